@@ -0,0 +1,60 @@
+//! Compares the allocation-free `HitQuery::hits` with the `List` based `Shape::intersects`.
+//! Run it with `cargo bench`. It has no dependencies: every query is timed with `std::time::Instant`.
+
+use geom3::{Aabb, HitQuery, Line3, Plane, Shape, Sphere, Triangle3};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use vector3::Vector3;
+
+const RAYS: usize = 1 << 16;
+const ROUNDS: usize = 20;
+
+// Rays from a grid of points in front of the shapes, all of them pointing to +Z
+fn rays() -> Vec<Line3> {
+    let side: usize = (RAYS as f64).sqrt() as usize;
+    (0..side * side)
+        .map(|i| {
+            let x: f64 = (i % side) as f64 / side as f64 * 4.0 - 2.0;
+            let y: f64 = (i / side) as f64 / side as f64 * 4.0 - 2.0;
+            Line3::new(&Vector3::new(x, y, -10.0), &Vector3::new(x, y, -9.0))
+        })
+        .collect()
+}
+
+// Best time of several rounds of a query over all the rays
+fn time<F: Fn(&Line3) -> f64>(rays: &[Line3], query: F) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start: Instant = Instant::now();
+            let mut sum: f64 = 0.0;
+            for ray in rays.iter() {
+                sum += query(black_box(ray));
+            }
+            black_box(sum);
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn compare<S: Shape + HitQuery>(name: &str, shape: &S, rays: &[Line3]) {
+    let list: Duration = time(rays, |ray| shape.intersects(ray).iter().sum());
+    let hits: Duration = time(rays, |ray| shape.hits(ray).iter().sum());
+    let per_ray = |d: Duration| d.as_nanos() as f64 / rays.len() as f64;
+    println!(
+        "{:<10} List: {:>7.2} ns/ray   Hits: {:>7.2} ns/ray   ({:.2}x)",
+        name, per_ray(list), per_ray(hits), list.as_secs_f64() / hits.as_secs_f64()
+    );
+}
+
+fn main() {
+    let rays: Vec<Line3> = rays();
+    compare("Sphere", &Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.5), &rays);
+    compare("Plane", &Plane::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 1.0)), &rays);
+    compare(
+        "Triangle3",
+        &Triangle3::new(&Vector3::new(-1.5, -1.5, 0.0), &Vector3::new(1.5, -1.5, 0.0), &Vector3::new(0.0, 1.5, 0.0)),
+        &rays,
+    );
+    compare("Aabb", &Aabb::new(&Vector3::new(-1.0, -1.0, -1.0), &Vector3::new(1.0, 1.0, 1.0)), &rays);
+}
@@ -0,0 +1,234 @@
+use crate::base::{Shape, Intersection, ClosestPoint, Support, Bounded};
+use crate::line3::Line3;
+use list::List;
+use vector3::Vector3;
+
+use std::fmt;
+
+/// Axis aligned box in 3D space.
+#[derive(Clone, Copy)]
+pub struct Aabb {
+    pub min: Vector3, // Corner of the box with the minimum coordinates
+    pub max: Vector3, // Corner of the box with the maximum coordinates
+}
+
+impl Aabb {
+    /// Creates a new `Aabb`.
+    /// The box is defined by two opposite corners, `a` and `b`, in any order.
+    pub fn new(a: &Vector3, b: &Vector3) -> Aabb {
+        Self {
+            min: Vector3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Vector3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// Creates the smallest `Aabb` containing all the given points.
+    pub fn from_points(points: &[Vector3]) -> Aabb {
+        if points.is_empty() {
+            panic!("The box cannot be defined by an empty list of points.");
+        }
+        points.iter().fold(Aabb::new(&points[0], &points[0]), |aabb, p| aabb.merge(&Aabb::new(p, p)))
+    }
+
+    /// Returns the smallest `Aabb` containing both boxes.
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        Self {
+            min: Vector3::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y), self.min.z.min(other.min.z)),
+            max: Vector3::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y), self.max.z.max(other.max.z)),
+        }
+    }
+
+    /// Returns the center of the box.
+    pub fn center(&self) -> Vector3 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Returns the half of the size of the box in each axis.
+    pub fn half_extents(&self) -> Vector3 {
+        (self.max - self.min) / 2.0
+    }
+
+    /// Returns the box grown by `margin` in every direction.
+    pub fn expand(&self, margin: f64) -> Aabb {
+        let m: Vector3 = Vector3::new(margin, margin, margin);
+        Aabb::new(&(self.min - m), &(self.max + m))
+    }
+
+    /// Returns the eight vertexes of the box.
+    pub fn vertices(&self) -> [Vector3; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            Vector3::new(min.x, min.y, min.z),
+            Vector3::new(max.x, min.y, min.z),
+            Vector3::new(min.x, max.y, min.z),
+            Vector3::new(max.x, max.y, min.z),
+            Vector3::new(min.x, min.y, max.z),
+            Vector3::new(max.x, min.y, max.z),
+            Vector3::new(min.x, max.y, max.z),
+            Vector3::new(max.x, max.y, max.z),
+        ]
+    }
+
+    /// Returns true if the point is inside the box (or in its surface).
+    pub fn contains(&self, p: &Vector3) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x &&
+        p.y >= self.min.y && p.y <= self.max.y &&
+        p.z >= self.min.z && p.z <= self.max.z
+    }
+
+    /// Returns the point of the box (including its interior) closest to a given point.
+    /// If the point is inside the box, it is returned itself.
+    pub fn clamp(&self, p: &Vector3) -> Vector3 {
+        Vector3::new(
+            p.x.clamp(self.min.x, self.max.x),
+            p.y.clamp(self.min.y, self.max.y),
+            p.z.clamp(self.min.z, self.max.z),
+        )
+    }
+
+    /// Returns the signed distance between a point and the surface of the box.
+    /// It is positive outside the box, and negative inside.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        if self.contains(p) {
+            -self.face_distances(p).iter().fold(f64::INFINITY, |min, &d| min.min(d))
+        } else {
+            self.clamp(p).distance_to(*p)
+        }
+    }
+
+    /// Returns the entry and exit lambdas of a line through the box (slab method).
+    /// If the line doesn´t cross the box, it returns Option None.
+    /// If the line just touches an edge or a vertex, entry and exit lambdas are equal.
+    pub fn entry_exit(&self, line: &Line3) -> Option<(f64, f64)> {
+        let mut entry: f64 = f64::NEG_INFINITY;
+        let mut exit: f64 = f64::INFINITY;
+        for (a, v, min, max) in [
+            (line.a.x, line.v.x, self.min.x, self.max.x),
+            (line.a.y, line.v.y, self.min.y, self.max.y),
+            (line.a.z, line.v.z, self.min.z, self.max.z),
+        ] {
+            if v == 0. {
+                if a < min || a > max {
+                    return None;
+                }
+                continue;
+            }
+            let l1: f64 = (min - a) / v;
+            let l2: f64 = (max - a) / v;
+            entry = entry.max(l1.min(l2));
+            exit = exit.min(l1.max(l2));
+        }
+
+        if entry > exit {
+            return None;
+        }
+        Some((entry, exit))
+    }
+
+    // Distances from an inner point to each face: -x, +x, -y, +y, -z, +z
+    fn face_distances(&self, p: &Vector3) -> [f64; 6] {
+        [
+            p.x - self.min.x, self.max.x - p.x,
+            p.y - self.min.y, self.max.y - p.y,
+            p.z - self.min.z, self.max.z - p.z,
+        ]
+    }
+
+    // Index of the face (as in face_distances) whose plane is closest to the point
+    fn closest_face(&self, p: &Vector3) -> usize {
+        let distances: [f64; 6] = self.face_distances(p);
+        (0..6).min_by(|&i, &j| distances[i].abs().total_cmp(&distances[j].abs())).unwrap()
+    }
+}
+
+impl ClosestPoint for Aabb {
+    /// Returns the point of the surface of the box closest to a given point.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        if !self.contains(p) {
+            return self.clamp(p);
+        }
+        // Inside the box: project on the closest face
+        let mut q: Vector3 = *p;
+        match self.closest_face(p) {
+            0 => q.x = self.min.x,
+            1 => q.x = self.max.x,
+            2 => q.y = self.min.y,
+            3 => q.y = self.max.y,
+            4 => q.z = self.min.z,
+            _ => q.z = self.max.z,
+        }
+        q
+    }
+}
+
+impl Support for Aabb {
+    /// Returns the vertex of the box farthest in a given direction.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        Vector3::new(
+            if dir.x >= 0. { self.max.x } else { self.min.x },
+            if dir.y >= 0. { self.max.y } else { self.min.y },
+            if dir.z >= 0. { self.max.z } else { self.min.z },
+        )
+    }
+}
+
+impl Bounded for Aabb {
+    fn bounding_box(&self) -> Aabb {
+        *self
+    }
+}
+
+impl Shape for Aabb {
+    /// Returns the outward normal (normalized) of the box at a given point.
+    /// The face used is the one closest to the point (the point should be in the surface of the box).
+    fn normal(&self, point: &Vector3) -> Vector3 {
+        match self.closest_face(point) {
+            0 => Vector3::new(-1.0, 0.0, 0.0),
+            1 => Vector3::new(1.0, 0.0, 0.0),
+            2 => Vector3::new(0.0, -1.0, 0.0),
+            3 => Vector3::new(0.0, 1.0, 0.0),
+            4 => Vector3::new(0.0, 0.0, -1.0),
+            _ => Vector3::new(0.0, 0.0, 1.0),
+        }
+    }
+
+    /// Returns the intersections of the box with a line.
+    /// If the line doesn´t cross the box, it returns an empty list.
+    /// If the line crosses the box, it returns a list with the entry and exit lambdas (in that order).
+    /// If the line just touches an edge or a vertex, it returns a list with the single lambda value.
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        let mut intersections: List<f64> = List::<f64>::new();
+
+        if let Some((entry, exit)) = self.entry_exit(line) {
+            if exit != entry {
+                intersections.push(exit);
+            }
+            intersections.push(entry);
+        }
+        intersections
+    }
+
+    /// Returns the closest positive intersection of the box with a line.
+    /// If the line doesn´t collide the box, it returns Option None.
+    /// If the line starts inside the box, the exit lambda is returned.
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        let (entry, exit) = self.entry_exit(line)?;
+        if entry > 0. {
+            Some(Intersection::new(entry, None))
+        } else if exit > 0. {
+            Some(Intersection::new(exit, None))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Aabb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Min ({}, {}, {}) Max ({}, {}, {})",
+            self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z
+        )
+    }
+}
@@ -1,3 +1,4 @@
+use crate::aabb::Aabb;
 use crate::line3::Line3;
 use list::List;
 use vector3::Vector3;
@@ -19,6 +20,51 @@ impl Intersection {
     }
 }
 
+/// Random point of the surface of a shape, as returned by the sampling functions.
+/// `pdf` is the probability density of the point: per unit of area for the uniform sampling of a surface, and per
+/// unit of solid angle for the sampling of the solid angle of a shape seen from a point.
+#[derive(Clone, Copy)]
+pub struct SurfaceSample {
+    pub point: Vector3,
+    pub normal: Vector3,
+    pub pdf: f64,
+}
+
+impl SurfaceSample {
+    /// Creates a new `SurfaceSample`.
+    pub fn new(point: &Vector3, normal: &Vector3, pdf: f64) -> SurfaceSample {
+        Self { point: *point, normal: *normal, pdf }
+    }
+}
+
+/// Returns two unit vectors `(u, v)` that, together with the unit vector `n`, define an orthonormal basis.
+/// The basis is right handed (`u x v = n`), so anticlockwise in the (u, v) plane is anticlockwise around `n`.
+pub(crate) fn orthonormal_basis(n: &Vector3) -> (Vector3, Vector3) {
+    // Use the axis less aligned with n as helper, to avoid numerical issues
+    let helper: Vector3 = if n.x.abs() < 0.9 {
+        Vector3::new(1.0, 0.0, 0.0)
+    } else {
+        Vector3::new(0.0, 1.0, 0.0)
+    };
+    let u: Vector3 = helper.cross(n).normalize();
+    let v: Vector3 = n.cross(&u);
+    (u, v)
+}
+
+/// Returns the point of the list with the maximum dot product with `dir` (the support of their convex hull).
+pub(crate) fn farthest_point(points: &[Vector3], dir: &Vector3) -> Vector3 {
+    let mut farthest: Vector3 = points[0];
+    let mut max: f64 = farthest.dot(dir);
+    for p in points[1..].iter() {
+        let dot: f64 = p.dot(dir);
+        if dot > max {
+            max = dot;
+            farthest = *p;
+        }
+    }
+    farthest
+}
+
 pub trait Shape {
     /// Returns the normal (normalized) of the shape at a given point.
     fn normal(&self, _point: &Vector3) -> Vector3;
@@ -44,3 +90,26 @@ pub trait Shape {
     ///     * The normal of the shape at the point of intersection with shape.normal(point).
     fn closest_intersection(&self, line: &Line3) -> Option<Intersection>;
 }
+
+pub trait ClosestPoint {
+    /// Returns the point of the shape closest to a given point.
+    /// For shapes with volume (like spheres or tetrahedrons), the point is in their surface.
+    fn closest_point(&self, p: &Vector3) -> Vector3;
+
+    /// Returns the (unsigned) distance between a point and the shape, that is the distance to its closest point.
+    fn dist_point(&self, p: &Vector3) -> f64 {
+        self.closest_point(p).distance_to(*p)
+    }
+}
+
+pub trait Support {
+    /// Returns the point of the (convex) shape farthest in a given direction, that is the point with the maximum
+    /// dot product with `dir`. This is all GJK and EPA need to know about a shape.
+    /// The direction doesn´t need to be normalized.
+    fn support(&self, dir: &Vector3) -> Vector3;
+}
+
+pub trait Bounded {
+    /// Returns an axis aligned box that contains the whole shape (the smallest one, when it is cheap to calculate).
+    fn bounding_box(&self) -> Aabb;
+}
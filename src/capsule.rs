@@ -0,0 +1,87 @@
+use crate::aabb::Aabb;
+use crate::base::{ClosestPoint, Support, Bounded, orthonormal_basis};
+use crate::line3::closest_point_segment;
+use vector3::Vector3;
+
+use std::fmt;
+
+/// A capsule in 3D space: all the points within a distance `r` of the segment from `a` to `b`.
+#[derive(Clone, Copy)]
+pub struct Capsule {
+    pub a: Vector3, // First end of the inner segment
+    pub b: Vector3, // Second end of the inner segment
+    pub r: f64,     // Radius of the capsule
+}
+
+impl Capsule {
+    /// Creates a new `Capsule`.
+    /// The capsule is defined by the ends of its inner segment, `a` and `b`, and its radius, `r`.
+    pub fn new(a: &Vector3, b: &Vector3, r: f64) -> Capsule {
+        if r < 0. {
+            panic!("The radius of the capsule cannot be negative");
+        }
+        Self { a: *a, b: *b, r }
+    }
+
+    /// Returns true if the point is inside the capsule (or in its surface).
+    pub fn contains(&self, p: &Vector3) -> bool {
+        closest_point_segment(&self.a, &self.b, p).distance_to(*p) <= self.r
+    }
+
+    /// Returns the signed distance between a point and the surface of the capsule.
+    /// It is positive outside the capsule, and negative inside.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        closest_point_segment(&self.a, &self.b, p).distance_to(*p) - self.r
+    }
+}
+
+impl ClosestPoint for Capsule {
+    /// Returns the point of the surface of the capsule closest to a given point.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let axis: Vector3 = closest_point_segment(&self.a, &self.b, p);
+        let dir: Vector3 = *p - axis;
+        if dir.is_zero() {
+            // In the inner segment: any direction perpendicular to it would do
+            let ab: Vector3 = self.b - self.a;
+            let perpendicular: Vector3 = if ab.is_zero() {
+                Vector3::new(1.0, 0.0, 0.0)
+            } else {
+                orthonormal_basis(&ab.normalize()).0
+            };
+            return axis + perpendicular * self.r;
+        }
+        axis + dir.normalize() * self.r
+    }
+
+    /// Returns the (unsigned) distance between a point and the surface of the capsule
+    fn dist_point(&self, p: &Vector3) -> f64 {
+        self.signed_dist_point(p).abs()
+    }
+}
+
+impl Support for Capsule {
+    /// Returns the point of the surface of the capsule farthest in a given direction.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        let end: Vector3 = if self.a.dot(dir) >= self.b.dot(dir) { self.a } else { self.b };
+        if dir.is_zero() {
+            return end;
+        }
+        end + dir.normalize() * self.r
+    }
+}
+
+impl Bounded for Capsule {
+    fn bounding_box(&self) -> Aabb {
+        Aabb::new(&self.a, &self.b).expand(self.r)
+    }
+}
+
+impl fmt::Display for Capsule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "A ({}, {}, {}) --> B ({}, {}, {}) Radius = {}",
+            self.a.x, self.a.y, self.a.z, self.b.x, self.b.y, self.b.z, self.r
+        )
+    }
+}
@@ -0,0 +1,73 @@
+use crate::base::{ClosestPoint, orthonormal_basis};
+use crate::plane::Plane;
+use vector3::Vector3;
+
+use std::f64::consts::PI;
+use std::fmt;
+
+/// A circle in 3D space.
+#[derive(Clone, Copy)]
+pub struct Circle3 {
+    pub c: Vector3, // Center of the circle
+    pub n: Vector3, // Normal of the plane of the circle (already normalized)
+    pub r: f64,     // Radius of the circle
+
+    pub(crate) u: Vector3, // Orthonormal basis of the plane of the circle, precalculated for performance
+    pub(crate) v: Vector3,
+}
+
+impl Circle3 {
+    /// Creates a new `Circle3`.
+    /// The circle is defined by its center, `c`, the normal of the plane that contains it, `n`, and its radius, `r`.
+    pub fn new(c: &Vector3, n: &Vector3, r: f64) -> Circle3 {
+        if n.is_zero() {
+            panic!("The normal of the circle cannot be zero");
+        }
+        if r < 0. {
+            panic!("The radius of the circle cannot be negative");
+        }
+        let n: Vector3 = n.normalize();
+        let (u, v) = orthonormal_basis(&n);
+        Self { c: *c, n, r, u, v }
+    }
+
+    /// Returns the plane that contains the circle.
+    pub fn plane(&self) -> Plane {
+        Plane::new(&self.c, &self.n)
+    }
+
+    /// Calculates a point in the circle, given the angle (in radians).
+    /// The angles go anticlockwise around the normal, starting in an arbitrary (but fixed) direction.
+    pub fn calc_point(&self, theta: f64) -> Vector3 {
+        self.c + (self.u * theta.cos() + self.v * theta.sin()) * self.r
+    }
+
+    /// Returns `count` points evenly spaced along the circle, starting at angle 0.
+    pub fn sample(&self, count: usize) -> Vec<Vector3> {
+        (0..count).map(|i| self.calc_point(2.0 * PI * i as f64 / count as f64)).collect()
+    }
+}
+
+impl ClosestPoint for Circle3 {
+    /// Returns the point of the circle (the curve, not the disk) closest to a given point.
+    /// If the point is in the axis of the circle, all the points are at the same distance: the one with angle 0
+    /// is returned.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let cp: Vector3 = *p - self.c;
+        let in_plane: Vector3 = cp - self.n * cp.dot(&self.n);
+        if in_plane.is_zero() {
+            return self.calc_point(0.0);
+        }
+        self.c + in_plane.normalize() * self.r
+    }
+}
+
+impl fmt::Display for Circle3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "C ({}, {}, {}) --> N ({}, {}, {}) Radius = {}",
+            self.c.x, self.c.y, self.c.z, self.n.x, self.n.y, self.n.z, self.r
+        )
+    }
+}
@@ -0,0 +1,307 @@
+use crate::base::ClosestPoint;
+use crate::line3::{Line3, closest_point_segment};
+use crate::obb::Obb;
+use crate::plane::Plane;
+use crate::sphere::Sphere;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+// Maximum number of contact points of a manifold
+const MAX_CONTACTS: usize = 4;
+
+// Tolerance to discard the axes of the separating axis test built with (almost) parallel edges
+const PARALLEL_TOLERANCE: f64 = 1e-9;
+
+// Distance (relative to the size of the manifold) to consider two contact points the same one
+const DUPLICATE_TOLERANCE: f64 = 1e-9;
+
+// Edge axes must be this much better than the face ones to be chosen, for stable manifolds with resting boxes
+const EDGE_BIAS: f64 = 0.95;
+
+/// Contact point between two overlapping (or touching) shapes.
+/// Moving the second shape `depth` units along `normal` (or the first one in the opposite direction) separates them.
+#[derive(Clone, Copy, Debug)]
+pub struct Contact {
+    pub point: Vector3,  // Contact point, halfway between the surfaces of both shapes
+    pub normal: Vector3, // Contact normal (normalized), from the first shape to the second one
+    pub depth: f64,      // Penetration depth (zero if the shapes just touch)
+}
+
+impl Contact {
+    /// Creates a new `Contact`.
+    /// The contact is defined by the contact point, the normal (from the first shape to the second one) and the depth.
+    pub fn new(point: &Vector3, normal: &Vector3, depth: f64) -> Contact {
+        Self { point: *point, normal: *normal, depth }
+    }
+
+    // The same contact, seen from the other shape
+    fn reversed(&self) -> Contact {
+        Contact::new(&self.point, &(self.normal * -1.0), self.depth)
+    }
+}
+
+/// Contact generation between two shapes, for rigid body simulation.
+/// Planes are solid half-spaces: their normal points out of the solid side.
+/// Axis aligned boxes can be used through `Obb::from`.
+pub trait Contacts<T> {
+    /// Returns the contact points between both shapes (up to 4), or an empty vector if they don´t overlap.
+    fn contacts(&self, other: &T) -> Vec<Contact>;
+}
+
+impl Contacts<Sphere> for Sphere {
+    fn contacts(&self, other: &Sphere) -> Vec<Contact> {
+        let d: Vector3 = other.c - self.c;
+        let dist: f64 = d.magnitude();
+        let depth: f64 = self.r + other.r - dist;
+        if depth < 0. {
+            return Vec::new();
+        }
+        // Concentric spheres: any direction separates them
+        let normal: Vector3 = if dist == 0. { Vector3::new(1.0, 0.0, 0.0) } else { d / dist };
+        vec![Contact::new(&(self.c + normal * (self.r - depth / 2.0)), &normal, depth)]
+    }
+}
+
+impl Contacts<Plane> for Sphere {
+    fn contacts(&self, other: &Plane) -> Vec<Contact> {
+        let dist: f64 = other.signed_dist_point(&self.c);
+        if dist > self.r {
+            return Vec::new();
+        }
+        // Halfway between the deepest point of the sphere and the plane
+        let point: Vector3 = self.c - other.n * ((self.r + dist) / 2.0);
+        vec![Contact::new(&point, &(other.n * -1.0), self.r - dist)]
+    }
+}
+
+impl Contacts<Triangle3> for Sphere {
+    fn contacts(&self, other: &Triangle3) -> Vec<Contact> {
+        let closest: Vector3 = other.closest_point(&self.c);
+        let d: Vector3 = closest - self.c;
+        let dist: f64 = d.magnitude();
+        if dist > self.r {
+            return Vec::new();
+        }
+        // Center in the triangle: push the triangle to its back side
+        let normal: Vector3 = if dist == 0. { other.plane.n * -1.0 } else { d / dist };
+        let point: Vector3 = (closest + self.c + normal * self.r) / 2.0;
+        vec![Contact::new(&point, &normal, self.r - dist)]
+    }
+}
+
+impl Contacts<Plane> for Obb {
+    fn contacts(&self, other: &Plane) -> Vec<Contact> {
+        let normal: Vector3 = other.n * -1.0;
+        let contacts: Vec<Contact> = self.vertices().iter()
+            .map(|v| (v, other.signed_dist_point(v)))
+            .filter(|&(_, dist)| dist <= 0.)
+            .map(|(v, dist)| Contact::new(&(*v - other.n * (dist / 2.0)), &normal, -dist))
+            .collect();
+        reduce(contacts, &normal)
+    }
+}
+
+impl Contacts<Obb> for Obb {
+    /// Finds the axis of minimum penetration with the separating axis test. If it is the normal of a face, the
+    /// closest face of the other box is clipped against it (up to 4 points); if it is built with two edges, the
+    /// contact is the closest point between them.
+    fn contacts(&self, other: &Obb) -> Vec<Contact> {
+        let d: Vector3 = other.c - self.c;
+        let penetration = |axis: &Vector3| -> f64 {
+            self.radius(axis) + other.radius(axis) - d.dot(axis).abs()
+        };
+
+        // Face axes of both boxes
+        let mut best: Option<(f64, Axis)> = None;
+        for i in 0..3 {
+            for (axis, kind) in [(self.axes[i], Axis::FaceA(i)), (other.axes[i], Axis::FaceB(i))] {
+                let depth: f64 = penetration(&axis);
+                if depth < 0. {
+                    return Vec::new();
+                }
+                if best.is_none_or(|(min, _)| depth < min) {
+                    best = Some((depth, kind));
+                }
+            }
+        }
+        // Axes built with an edge of each box
+        for i in 0..3 {
+            for j in 0..3 {
+                let axis: Vector3 = self.axes[i].cross(&other.axes[j]);
+                if axis.magnitude() <= PARALLEL_TOLERANCE {
+                    continue;
+                }
+                let depth: f64 = penetration(&axis.normalize());
+                if depth < 0. {
+                    return Vec::new();
+                }
+                if best.is_none_or(|(min, _)| depth < min * EDGE_BIAS) {
+                    best = Some((depth, Axis::Edges(i, j)));
+                }
+            }
+        }
+
+        let (depth, kind) = best.unwrap();
+        match kind {
+            Axis::FaceA(i) => {
+                let normal: Vector3 = if self.axes[i].dot(&d) >= 0. { self.axes[i] } else { self.axes[i] * -1.0 };
+                face_contacts(self, other, i, &normal)
+            }
+            Axis::FaceB(j) => {
+                let normal: Vector3 = if other.axes[j].dot(&d) <= 0. { other.axes[j] } else { other.axes[j] * -1.0 };
+                face_contacts(other, self, j, &normal).iter().map(Contact::reversed).collect()
+            }
+            Axis::Edges(i, j) => {
+                let mut normal: Vector3 = self.axes[i].cross(&other.axes[j]).normalize();
+                if normal.dot(&d) < 0. {
+                    normal = normal * -1.0;
+                }
+                let (a0, a1) = self.edge(i, &normal);
+                let (b0, b1) = other.edge(j, &(normal * -1.0));
+                let (pa, pb) = closest_segments(&a0, &a1, &b0, &b1);
+                vec![Contact::new(&((pa + pb) / 2.0), &normal, depth)]
+            }
+        }
+    }
+}
+
+// Axis of minimum penetration between two boxes
+#[derive(Clone, Copy)]
+enum Axis {
+    FaceA(usize),
+    FaceB(usize),
+    Edges(usize, usize),
+}
+
+impl Obb {
+    // Half of the length of the projection of the box over a (normalized) axis
+    fn radius(&self, axis: &Vector3) -> f64 {
+        self.half_extents.x * self.axes[0].dot(axis).abs() +
+        self.half_extents.y * self.axes[1].dot(axis).abs() +
+        self.half_extents.z * self.axes[2].dot(axis).abs()
+    }
+
+    // The edge parallel to the local axis `axis` farthest in a given direction
+    fn edge(&self, axis: usize, dir: &Vector3) -> (Vector3, Vector3) {
+        let h: [f64; 3] = [self.half_extents.x, self.half_extents.y, self.half_extents.z];
+        let center: Vector3 = (0..3).filter(|&k| k != axis).fold(self.c, |p, k| {
+            let sign: f64 = if self.axes[k].dot(dir) >= 0. { 1.0 } else { -1.0 };
+            p + self.axes[k] * (sign * h[k])
+        });
+        (center - self.axes[axis] * h[axis], center + self.axes[axis] * h[axis])
+    }
+}
+
+// Contacts when the axis of minimum penetration is the normal of the face `face` of the reference box.
+// The face of the incident box most opposed to the normal is clipped against the sides of the reference face.
+fn face_contacts(reference: &Obb, incident: &Obb, face: usize, normal: &Vector3) -> Vec<Contact> {
+    let h_ref: [f64; 3] = [reference.half_extents.x, reference.half_extents.y, reference.half_extents.z];
+    let h_inc: [f64; 3] = [incident.half_extents.x, incident.half_extents.y, incident.half_extents.z];
+    let face_center: Vector3 = reference.c + *normal * h_ref[face];
+
+    let inc: usize = (0..3).max_by(|&i, &j| incident.axes[i].dot(normal).abs().total_cmp(&incident.axes[j].dot(normal).abs())).unwrap();
+    let sign: f64 = if incident.axes[inc].dot(normal) > 0. { -1.0 } else { 1.0 };
+    let inc_center: Vector3 = incident.c + incident.axes[inc] * (sign * h_inc[inc]);
+    let (k1, k2) = ((inc + 1) % 3, (inc + 2) % 3);
+    let (u, v) = (incident.axes[k1] * h_inc[k1], incident.axes[k2] * h_inc[k2]);
+    let mut polygon: Vec<Vector3> = vec![inc_center + u + v, inc_center - u + v, inc_center - u - v, inc_center + u - v];
+
+    // Clip against the four side planes of the reference face
+    for side in (0..3).filter(|&k| k != face) {
+        let axis: Vector3 = reference.axes[side];
+        let offset: f64 = reference.c.dot(&axis);
+        polygon = clip(&polygon, &axis, offset + h_ref[side]);
+        polygon = clip(&polygon, &(axis * -1.0), h_ref[side] - offset);
+    }
+
+    let contacts: Vec<Contact> = polygon.iter()
+        .map(|p| (p, (*p - face_center).dot(normal)))
+        .filter(|&(_, dist)| dist <= 0.)
+        .map(|(p, dist)| Contact::new(&(*p - *normal * (dist / 2.0)), normal, -dist))
+        .collect();
+    reduce(contacts, normal)
+}
+
+// Sutherland-Hodgman: keeps the part of the polygon with `p · n <= offset`
+fn clip(polygon: &[Vector3], n: &Vector3, offset: f64) -> Vec<Vector3> {
+    let mut clipped: Vec<Vector3> = Vec::new();
+    for (i, p) in polygon.iter().enumerate() {
+        let q: &Vector3 = &polygon[(i + 1) % polygon.len()];
+        let dp: f64 = p.dot(n) - offset;
+        let dq: f64 = q.dot(n) - offset;
+        if dp <= 0. {
+            clipped.push(*p);
+        }
+        if (dp < 0. && dq > 0.) || (dp > 0. && dq < 0.) {
+            clipped.push(*p + (*q - *p) * (dp / (dp - dq)));
+        }
+    }
+    clipped
+}
+
+// Closest points between two segments (that can be degenerated to a point)
+fn closest_segments(a0: &Vector3, a1: &Vector3, b0: &Vector3, b1: &Vector3) -> (Vector3, Vector3) {
+    if a0 == a1 {
+        return (*a0, closest_point_segment(b0, b1, a0));
+    }
+    if b0 == b1 {
+        return (closest_point_segment(a0, a1, b0), *b0);
+    }
+    let approach = Line3::new(a0, a1).closest_approach_segment(&Line3::new(b0, b1));
+    (approach.p1, approach.p2)
+}
+
+// Keeps the most relevant contacts: the deepest one, the farthest from it, and the two that maximize the area
+// of the manifold (one at each side of the line that joins the first two).
+// Repeated points (closer than the tolerance) are kept once, and if all the points are aligned there is nothing at
+// the sides of the line, so fewer than 4 contacts are returned.
+fn reduce(contacts: Vec<Contact>, normal: &Vector3) -> Vec<Contact> {
+    let Some(first) = contacts.iter().max_by(|c1, c2| c1.depth.total_cmp(&c2.depth)).copied() else {
+        return contacts;
+    };
+    let size: f64 = contacts.iter().map(|c| c.point.distance_to(first.point)).fold(0.0, f64::max);
+    let eps: f64 = DUPLICATE_TOLERANCE * size;
+
+    let mut unique: Vec<Contact> = Vec::new();
+    for contact in contacts.iter() {
+        if unique.iter().all(|c| c.point.distance_to(contact.point) > eps) {
+            unique.push(*contact);
+        }
+    }
+    if unique.len() <= MAX_CONTACTS {
+        return unique;
+    }
+
+    let second: Contact = *unique.iter()
+        .max_by(|c1, c2| c1.point.distance_to(first.point).total_cmp(&c2.point.distance_to(first.point)))
+        .unwrap();
+    let signed_area = |c: &Contact| -> f64 {
+        (second.point - first.point).cross(&(c.point - first.point)).dot(normal)
+    };
+    let third: Contact = *unique.iter().max_by(|c1, c2| signed_area(c1).total_cmp(&signed_area(c2))).unwrap();
+    let fourth: Contact = *unique.iter().min_by(|c1, c2| signed_area(c1).total_cmp(&signed_area(c2))).unwrap();
+
+    let mut reduced: Vec<Contact> = vec![first, second];
+    if signed_area(&third) > eps * size {
+        reduced.push(third);
+    }
+    if signed_area(&fourth) < -eps * size {
+        reduced.push(fourth);
+    }
+    reduced
+}
+
+// The contacts are symmetric, so the reversed pairs just swap the arguments and flip the normals
+macro_rules! reversed_contacts {
+    ($($a:ty => $b:ty),* $(,)?) => {
+        $(
+            impl Contacts<$a> for $b {
+                fn contacts(&self, other: &$a) -> Vec<Contact> {
+                    other.contacts(self).iter().map(Contact::reversed).collect()
+                }
+            }
+        )*
+    };
+}
+
+reversed_contacts!(Sphere => Plane, Sphere => Triangle3, Obb => Plane);
@@ -0,0 +1,34 @@
+use crate::aabb::Aabb;
+use crate::base::{Support, Bounded, farthest_point};
+use vector3::Vector3;
+
+/// The convex hull of a set of points in 3D space.
+/// It is defined implicitly by its points (that don´t need to be the vertexes of the hull): this is enough for the
+/// algorithms that only need the support function, like GJK and EPA.
+#[derive(Clone)]
+pub struct ConvexHull {
+    pub points: Vec<Vector3>, // Points whose convex hull is the shape
+}
+
+impl ConvexHull {
+    /// Creates a new `ConvexHull` from a list of points (at least one).
+    pub fn new(points: &[Vector3]) -> ConvexHull {
+        if points.is_empty() {
+            panic!("The convex hull cannot be defined by an empty list of points.");
+        }
+        Self { points: points.to_vec() }
+    }
+}
+
+impl Support for ConvexHull {
+    /// Returns the point of the hull farthest in a given direction.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        farthest_point(&self.points, dir)
+    }
+}
+
+impl Bounded for ConvexHull {
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&self.points)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, ClosestPoint, Bounded};
+use crate::interval::{Interval, Intervals};
+use crate::line3::Line3;
+use list::List;
+use vector3::Vector3;
+
+/// Boolean operation of a `Csg` node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CsgOp {
+    Union,        // Points inside any of the operands
+    Intersection, // Points inside both operands
+    Difference,   // Points inside the first operand, but not inside the second one
+}
+
+impl CsgOp {
+    fn inside(&self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            CsgOp::Union => in_a || in_b,
+            CsgOp::Intersection => in_a && in_b,
+            CsgOp::Difference => in_a && !in_b,
+        }
+    }
+}
+
+/// Constructive solid geometry: union, intersection or difference of two closed shapes, without meshing them.
+/// The intersections with a line are calculated merging the intervals of the line inside each operand. The
+/// operands can be `Csg` nodes too, to build trees of operations.
+#[derive(Clone)]
+pub struct Csg<A, B> {
+    pub op: CsgOp, // Boolean operation
+    pub a: A,      // First operand
+    pub b: B,      // Second operand
+}
+
+impl<A, B> Csg<A, B> {
+    /// Creates a new `Csg` node.
+    pub fn new(op: CsgOp, a: A, b: B) -> Csg<A, B> {
+        Self { op, a, b }
+    }
+
+    /// Creates the union of two shapes.
+    pub fn union(a: A, b: B) -> Csg<A, B> {
+        Self::new(CsgOp::Union, a, b)
+    }
+
+    /// Creates the intersection of two shapes.
+    pub fn intersection(a: A, b: B) -> Csg<A, B> {
+        Self::new(CsgOp::Intersection, a, b)
+    }
+
+    /// Creates the difference of two shapes (`a` minus `b`).
+    pub fn difference(a: A, b: B) -> Csg<A, B> {
+        Self::new(CsgOp::Difference, a, b)
+    }
+
+    // The surfaces of the second operand of a difference are seen from inside, so their normals are flipped
+    fn flip_b(&self) -> f64 {
+        if self.op == CsgOp::Difference { -1.0 } else { 1.0 }
+    }
+}
+
+impl<A: Intervals, B: Intervals> Intervals for Csg<A, B> {
+    fn intervals(&self, line: &Line3) -> Vec<Interval> {
+        // Boundaries of the operands: (lambda, from b, entering, normal of the surface of the result)
+        let mut events: Vec<(f64, bool, bool, Vector3)> = Vec::new();
+        for (from_b, intervals) in [(false, self.a.intervals(line)), (true, self.b.intervals(line))] {
+            let flip: f64 = if from_b { self.flip_b() } else { 1.0 };
+            for i in intervals {
+                events.push((i.entry, from_b, true, i.entry_normal * flip));
+                events.push((i.exit, from_b, false, i.exit_normal * flip));
+            }
+        }
+        // At the same lambda, entries first (so touching solids are merged)
+        events.sort_by(|e1, e2| e1.0.total_cmp(&e2.0).then(e2.2.cmp(&e1.2)));
+
+        let mut intervals: Vec<Interval> = Vec::new();
+        let (mut in_a, mut in_b) = (false, false);
+        let mut entry: (f64, Vector3) = (0.0, Vector3::new(0.0, 0.0, 0.0));
+        for (lambda, from_b, entering, normal) in events {
+            let was_inside: bool = self.op.inside(in_a, in_b);
+            if from_b { in_b = entering } else { in_a = entering }
+            let inside: bool = self.op.inside(in_a, in_b);
+            if inside && !was_inside {
+                entry = (lambda, normal);
+            } else if was_inside && !inside && lambda > entry.0 {
+                // Leaving b in a difference enters the result, and the other way around: the flipped normals of b
+                // point out of the result in both cases
+                intervals.push(Interval::new(entry.0, lambda, &entry.1, &normal));
+            }
+        }
+        intervals
+    }
+}
+
+impl<A: ClosestPoint, B: ClosestPoint> ClosestPoint for Csg<A, B> {
+    /// Returns the point of the surfaces of the operands closest to a given point.
+    /// The surface of the result is part of them, so it is exact for points in the surface of the result (like the
+    /// ones passed to `normal`), but other points can get a point of a surface removed by the operation.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let pa: Vector3 = self.a.closest_point(p);
+        let pb: Vector3 = self.b.closest_point(p);
+        if pa.distance_to(*p) <= pb.distance_to(*p) { pa } else { pb }
+    }
+}
+
+impl<A, B> Shape for Csg<A, B>
+where
+    A: Shape + Intervals + ClosestPoint,
+    B: Shape + Intervals + ClosestPoint,
+{
+    /// Returns the outward normal (normalized) of the result at a given point of its surface: the normal of the
+    /// operand whose surface contains the point (flipped for the second operand of a difference).
+    fn normal(&self, point: &Vector3) -> Vector3 {
+        if self.a.dist_point(point) <= self.b.dist_point(point) {
+            self.a.normal(point)
+        } else {
+            self.b.normal(point) * self.flip_b()
+        }
+    }
+
+    /// Returns the entry and exit lambdas of all the intervals of the line inside the result.
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        let mut intersections: List<f64> = List::<f64>::new();
+        for i in self.intervals(line).iter().rev() {
+            intersections.push(i.exit);
+            intersections.push(i.entry);
+        }
+        intersections
+    }
+
+    /// Returns the closest positive intersection with the surface of the result.
+    /// If the line starts inside the result, it is the exit lambda.
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        self.intervals(line).iter()
+            .flat_map(|i| [i.entry, i.exit])
+            .find(|&lambda| lambda > 0.)
+            .map(|lambda| Intersection::new(lambda, None))
+    }
+}
+
+impl<A: Bounded, B: Bounded> Bounded for Csg<A, B> {
+    /// The intersection and the difference are inside the first operand, so its box is used (it may not be the
+    /// smallest one).
+    fn bounding_box(&self) -> Aabb {
+        match self.op {
+            CsgOp::Union => self.a.bounding_box().merge(&self.b.bounding_box()),
+            CsgOp::Intersection | CsgOp::Difference => self.a.bounding_box(),
+        }
+    }
+}
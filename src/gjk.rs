@@ -0,0 +1,303 @@
+use crate::base::{Support, orthonormal_basis};
+use crate::triangle3::closest_barycentric;
+use vector3::Vector3;
+
+// Relative tolerance for the convergence of GJK (and for degenerated simplexes)
+const GJK_TOLERANCE: f64 = 1e-10;
+const GJK_MAX_ITERATIONS: usize = 64;
+
+// Relative tolerance for the convergence of EPA. Curved shapes (like spheres) converge slowly, so it is looser
+const EPA_TOLERANCE: f64 = 1e-8;
+const EPA_MAX_ITERATIONS: usize = 128;
+
+/// Result of the distance query between two separated convex shapes.
+#[derive(Clone, Copy, Debug)]
+pub struct Separation {
+    pub distance: f64,    // Minimum distance between both shapes
+    pub point_a: Vector3, // Point of the first shape closest to the second one
+    pub point_b: Vector3, // Point of the second shape closest to the first one
+}
+
+/// Result of the penetration query between two overlapping convex shapes.
+#[derive(Clone, Copy, Debug)]
+pub struct Penetration {
+    pub depth: f64,       // Penetration depth: minimum translation needed to separate both shapes
+    pub normal: Vector3,  // Contact normal (normalized), from the first shape to the second one
+    pub point_a: Vector3, // Deepest point of the first shape inside the second one
+    pub point_b: Vector3, // Deepest point of the second shape inside the first one
+}
+
+// Vertex of the Minkowski difference A - B, with the supporting points of each shape that produced it
+#[derive(Clone, Copy)]
+struct Vertex {
+    w: Vector3,
+    a: Vector3,
+    b: Vector3,
+}
+
+enum Gjk {
+    Separated(Separation),
+    Overlapping(Vec<Vertex>), // Simplex of the Minkowski difference that contains the origin
+}
+
+fn support<A: Support + ?Sized, B: Support + ?Sized>(shape_a: &A, shape_b: &B, dir: &Vector3) -> Vertex {
+    let a: Vector3 = shape_a.support(dir);
+    let b: Vector3 = shape_b.support(&(*dir * -1.0));
+    Vertex { w: a - b, a, b }
+}
+
+/// Calculates the distance between two convex shapes, and their closest points, with the GJK algorithm.
+/// If the shapes overlap (or touch), it returns Option None.
+pub fn gjk_distance<A: Support + ?Sized, B: Support + ?Sized>(shape_a: &A, shape_b: &B) -> Option<Separation> {
+    match gjk(shape_a, shape_b) {
+        Gjk::Separated(separation) => Some(separation),
+        Gjk::Overlapping(_) => None,
+    }
+}
+
+/// Returns true if two convex shapes overlap (or touch), using the GJK algorithm.
+pub fn gjk_overlaps<A: Support + ?Sized, B: Support + ?Sized>(shape_a: &A, shape_b: &B) -> bool {
+    matches!(gjk(shape_a, shape_b), Gjk::Overlapping(_))
+}
+
+/// Calculates the penetration of two overlapping convex shapes with the EPA algorithm.
+/// If the shapes don´t overlap, it returns Option None.
+/// Moving the second shape `depth` units along `normal` (or the first one in the opposite direction) separates them.
+pub fn epa_penetration<A: Support + ?Sized, B: Support + ?Sized>(shape_a: &A, shape_b: &B) -> Option<Penetration> {
+    match gjk(shape_a, shape_b) {
+        Gjk::Separated(_) => None,
+        Gjk::Overlapping(simplex) => Some(epa(shape_a, shape_b, simplex)),
+    }
+}
+
+// Point of the simplex given by its barycentric weights
+fn combine(simplex: &[Vertex], weights: &[f64]) -> Vertex {
+    let mut result: Vertex = Vertex { w: Vector3::new(0.0, 0.0, 0.0), a: Vector3::new(0.0, 0.0, 0.0), b: Vector3::new(0.0, 0.0, 0.0) };
+    for (vertex, &weight) in simplex.iter().zip(weights.iter()) {
+        result.w = result.w + vertex.w * weight;
+        result.a = result.a + vertex.a * weight;
+        result.b = result.b + vertex.b * weight;
+    }
+    result
+}
+
+fn gjk<A: Support + ?Sized, B: Support + ?Sized>(shape_a: &A, shape_b: &B) -> Gjk {
+    let first: Vertex = support(shape_a, shape_b, &Vector3::new(1.0, 0.0, 0.0));
+    let mut simplex: Vec<Vertex> = vec![first];
+    let mut weights: Vec<f64> = vec![1.0];
+    let mut v: Vector3 = first.w;
+    let mut scale: f64 = first.w.magnitude();
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        let qv: f64 = v.dot(&v);
+        if qv <= (GJK_TOLERANCE * scale).powi(2) {
+            return Gjk::Overlapping(simplex);
+        }
+
+        // New point of the Minkowski difference in the direction of the origin: if it isn´t closer, v is the closest
+        let new: Vertex = support(shape_a, shape_b, &(v * -1.0));
+        scale = scale.max(new.w.magnitude());
+        if qv - v.dot(&new.w) <= GJK_TOLERANCE * qv || simplex.iter().any(|s| s.w.distance_to(new.w) <= GJK_TOLERANCE * scale) {
+            break;
+        }
+        simplex.push(new);
+
+        let (reduced, reduced_weights, contains_origin) = closest_simplex(&simplex, GJK_TOLERANCE * scale);
+        if contains_origin {
+            return Gjk::Overlapping(simplex);
+        }
+        let closest: Vector3 = combine(&reduced, &reduced_weights).w;
+        if closest.dot(&closest) >= qv {
+            // No progress (numerical issues): v is as close as we can get
+            simplex.pop();
+            break;
+        }
+        simplex = reduced;
+        weights = reduced_weights;
+        v = closest;
+    }
+
+    let closest: Vertex = combine(&simplex, &weights);
+    Gjk::Separated(Separation { distance: closest.w.magnitude(), point_a: closest.a, point_b: closest.b })
+}
+
+// Closest point of the simplex to the origin, as the sub-simplex that contains it and its barycentric weights.
+// The flag is set if the origin is inside the simplex (only possible with 4 vertexes).
+fn closest_simplex(simplex: &[Vertex], eps: f64) -> (Vec<Vertex>, Vec<f64>, bool) {
+    let origin: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+    match simplex.len() {
+        1 => (simplex.to_vec(), vec![1.0], false),
+        2 => {
+            let (a, b) = (simplex[0], simplex[1]);
+            let ab: Vector3 = b.w - a.w;
+            let qab: f64 = ab.dot(&ab);
+            let t: f64 = if qab == 0. { 0.0 } else { -a.w.dot(&ab) / qab };
+            if t <= 0. {
+                (vec![a], vec![1.0], false)
+            } else if t >= 1. {
+                (vec![b], vec![1.0], false)
+            } else {
+                (vec![a, b], vec![1.0 - t, t], false)
+            }
+        }
+        3 => {
+            let (a, b, c) = (simplex[0], simplex[1], simplex[2]);
+            if (b.w - a.w).cross(&(c.w - a.w)).magnitude() <= eps * eps {
+                // Aligned vertexes: the closest point is in one of the edges
+                return closest_of(&[vec![a, b], vec![b, c], vec![a, c]], eps);
+            }
+            let bar: Vector3 = closest_barycentric(&a.w, &b.w, &c.w, &origin);
+            let mut reduced: Vec<Vertex> = Vec::new();
+            let mut weights: Vec<f64> = Vec::new();
+            for (vertex, weight) in [(a, bar.x), (b, bar.y), (c, bar.z)] {
+                if weight > 0. {
+                    reduced.push(vertex);
+                    weights.push(weight);
+                }
+            }
+            (reduced, weights, false)
+        }
+        _ => {
+            let (a, b, c, d) = (simplex[0], simplex[1], simplex[2], simplex[3]);
+            let (ab, ac, ad) = (b.w - a.w, c.w - a.w, d.w - a.w);
+            let vol6: f64 = ab.dot(&ac.cross(&ad));
+            if vol6.abs() > eps.powi(3) {
+                // Barycentric coordinates of the origin: all positive means it is inside
+                let ao: Vector3 = a.w * -1.0;
+                let bar_b: f64 = ao.dot(&ac.cross(&ad)) / vol6;
+                let bar_c: f64 = ab.dot(&ao.cross(&ad)) / vol6;
+                let bar_d: f64 = ab.dot(&ac.cross(&ao)) / vol6;
+                if bar_b >= 0. && bar_c >= 0. && bar_d >= 0. && bar_b + bar_c + bar_d <= 1. {
+                    return (simplex.to_vec(), vec![1.0 - bar_b - bar_c - bar_d, bar_b, bar_c, bar_d], true);
+                }
+            }
+            closest_of(&[vec![a, b, c], vec![a, b, d], vec![a, c, d], vec![b, c, d]], eps)
+        }
+    }
+}
+
+// The closest to the origin of several simplexes
+fn closest_of(simplexes: &[Vec<Vertex>], eps: f64) -> (Vec<Vertex>, Vec<f64>, bool) {
+    simplexes.iter()
+        .map(|simplex| closest_simplex(simplex, eps))
+        .min_by(|(s1, w1, _), (s2, w2, _)| {
+            let v1: Vector3 = combine(s1, w1).w;
+            let v2: Vector3 = combine(s2, w2).w;
+            v1.dot(&v1).total_cmp(&v2.dot(&v2))
+        })
+        .unwrap()
+}
+
+// Expanding Polytope Algorithm: grows the simplex that contains the origin towards the border of the Minkowski
+// difference, until the face closest to the origin is found
+fn epa<A: Support + ?Sized, B: Support + ?Sized>(shape_a: &A, shape_b: &B, mut vertices: Vec<Vertex>) -> Penetration {
+    let scale: f64 = vertices.iter().map(|v| v.w.magnitude()).fold(0.0, f64::max).max(
+        [Vector3::new(1.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0)].iter().map(|dir| support(shape_a, shape_b, dir).w.magnitude()).fold(0.0, f64::max)
+    );
+    let eps: f64 = GJK_TOLERANCE * scale;
+
+    // The origin can be in a vertex, edge or face of the GJK simplex: grow it to a proper tetrahedron
+    if vertices.len() == 1 {
+        let axes: [Vector3; 6] = [
+            Vector3::new(1.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, -1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 0.0, -1.0),
+        ];
+        if let Some(vertex) = axes.iter().map(|dir| support(shape_a, shape_b, dir)).find(|v| v.w.distance_to(vertices[0].w) > eps) {
+            vertices.push(vertex);
+        }
+    }
+    if vertices.len() == 2 {
+        let axis: Vector3 = (vertices[1].w - vertices[0].w).normalize();
+        let (u, v) = orthonormal_basis(&axis);
+        let found: Option<Vertex> = [u, u * -1.0, v, v * -1.0].iter().map(|dir| support(shape_a, shape_b, dir)).find(|vertex| {
+            let offset: Vector3 = vertex.w - vertices[0].w;
+            (offset - axis * offset.dot(&axis)).magnitude() > eps
+        });
+        if let Some(vertex) = found {
+            vertices.push(vertex);
+        }
+    }
+    if vertices.len() == 3 {
+        let n: Vector3 = (vertices[1].w - vertices[0].w).cross(&(vertices[2].w - vertices[0].w));
+        let found: Option<Vertex> = [n, n * -1.0].iter().map(|dir| support(shape_a, shape_b, dir))
+            .find(|vertex| (vertex.w - vertices[0].w).dot(&n).abs() > eps * n.magnitude());
+        if let Some(vertex) = found {
+            vertices.push(vertex);
+        }
+    }
+    if vertices.len() < 4 {
+        // Flat Minkowski difference (like coplanar triangles): they touch without volume, so there is no depth
+        let weights: Vec<f64> = vec![1.0 / vertices.len() as f64; vertices.len()];
+        let center: Vertex = combine(&vertices, &weights);
+        let normal: Vector3 = if vertices.len() == 3 {
+            (vertices[1].w - vertices[0].w).cross(&(vertices[2].w - vertices[0].w)).normalize()
+        } else {
+            Vector3::new(1.0, 0.0, 0.0)
+        };
+        return Penetration { depth: 0.0, normal, point_a: center.a, point_b: center.b };
+    }
+
+    // Faces of the polytope, oriented so their normals point outwards
+    let mut faces: Vec<[usize; 3]> = Vec::new();
+    for (face, opposite) in [([0, 1, 2], 3), ([0, 3, 1], 2), ([0, 2, 3], 1), ([1, 3, 2], 0)] {
+        let [i, j, k] = face;
+        let n: Vector3 = (vertices[j].w - vertices[i].w).cross(&(vertices[k].w - vertices[i].w));
+        faces.push(if n.dot(&(vertices[opposite].w - vertices[i].w)) > 0. { [i, k, j] } else { face });
+    }
+
+    let face_plane = |vertices: &Vec<Vertex>, face: &[usize; 3]| -> (Vector3, f64) {
+        let [i, j, k] = *face;
+        let n: Vector3 = (vertices[j].w - vertices[i].w).cross(&(vertices[k].w - vertices[i].w)).normalize();
+        (n, n.dot(&vertices[i].w))
+    };
+
+    let mut closest: ([usize; 3], Vector3, f64) = (faces[0], Vector3::new(1.0, 0.0, 0.0), f64::INFINITY);
+    for _ in 0..EPA_MAX_ITERATIONS {
+        closest = (faces[0], Vector3::new(1.0, 0.0, 0.0), f64::INFINITY);
+        for face in faces.iter() {
+            let (n, dist) = face_plane(&vertices, face);
+            if dist < closest.2 {
+                closest = (*face, n, dist);
+            }
+        }
+
+        let (_, n, dist) = closest;
+        let new: Vertex = support(shape_a, shape_b, &n);
+        if new.w.dot(&n) - dist <= EPA_TOLERANCE * scale {
+            break;
+        }
+
+        // Remove the faces visible from the new vertex, and connect it with the edges of the hole
+        vertices.push(new);
+        let index: usize = vertices.len() - 1;
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut kept: Vec<[usize; 3]> = Vec::new();
+        for face in faces.iter() {
+            let (n, dist) = face_plane(&vertices, face);
+            if n.dot(&new.w) - dist > eps {
+                for (p, q) in [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])] {
+                    // An edge shared by two visible faces is not in the border of the hole
+                    if let Some(shared) = edges.iter().position(|&(r, s)| r == q && s == p) {
+                        edges.remove(shared);
+                    } else {
+                        edges.push((p, q));
+                    }
+                }
+            } else {
+                kept.push(*face);
+            }
+        }
+        if edges.is_empty() {
+            break;
+        }
+        faces = kept;
+        faces.extend(edges.iter().map(|&(p, q)| [p, q, index]));
+    }
+
+    // Contact points: the projection of the origin on the closest face, in each shape
+    let ([i, j, k], normal, depth) = closest;
+    let bar: Vector3 = closest_barycentric(&vertices[i].w, &vertices[j].w, &vertices[k].w, &(normal * depth));
+    let contact: Vertex = combine(&[vertices[i], vertices[j], vertices[k]], &[bar.x, bar.y, bar.z]);
+    Penetration { depth: depth.max(0.0), normal, point_a: contact.a, point_b: contact.b }
+}
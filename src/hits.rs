@@ -0,0 +1,203 @@
+use crate::aabb::Aabb;
+use crate::line3::Line3;
+use crate::plane::Plane;
+use crate::polygon3::Polygon3;
+use crate::shape_kind::{ShapeKind, dispatch};
+use crate::sphere::Sphere;
+use crate::tetrahedron::Tetrahedron;
+use crate::transformed::Transformed;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+use std::fmt;
+
+// A line crosses the surface of a convex (or flat) shape at most twice
+const MAX_HITS: usize = 2;
+
+/// Intersections of a shape with a line (just the lambda values), sorted from the lowest lambda to the highest.
+/// Unlike the `List` returned by `Shape::intersects`, it is stored in place (no heap allocation): it has room for
+/// the two intersections of a line with a convex shape.
+#[derive(Clone, Copy)]
+pub struct Hits {
+    lambdas: [f64; MAX_HITS], // Lambdas of the intersections (only the first `len` are valid), sorted
+    len: usize,               // Number of intersections
+}
+
+impl Hits {
+    /// Maximum number of intersections.
+    pub const CAPACITY: usize = MAX_HITS;
+
+    /// Creates a new empty `Hits`.
+    pub fn new() -> Hits {
+        Self { lambdas: [0.0; MAX_HITS], len: 0 }
+    }
+
+    /// Adds an intersection, keeping them sorted.
+    /// It panics if there is no room for it (more than `CAPACITY` intersections).
+    pub fn push(&mut self, lambda: f64) {
+        if self.len == MAX_HITS {
+            panic!("There is no room for more than {} intersections", MAX_HITS);
+        }
+        let mut i: usize = self.len;
+        while i > 0 && self.lambdas[i - 1] > lambda {
+            self.lambdas[i] = self.lambdas[i - 1];
+            i -= 1;
+        }
+        self.lambdas[i] = lambda;
+        self.len += 1;
+    }
+
+    /// Returns the number of intersections.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no intersections.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the lambdas of the intersections, sorted.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.lambdas[..self.len]
+    }
+
+    /// Returns an iterator over the lambdas of the intersections, sorted.
+    pub fn iter(&self) -> std::slice::Iter<'_, f64> {
+        self.as_slice().iter()
+    }
+
+    /// Returns the closest positive lambda ("positive" means, in the direction of the director vector of the line).
+    pub fn first_positive(&self) -> Option<f64> {
+        self.iter().copied().find(|&lambda| lambda > 0.)
+    }
+}
+
+impl Default for Hits {
+    fn default() -> Hits {
+        Hits::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a Hits {
+    type Item = &'a f64;
+    type IntoIter = std::slice::Iter<'a, f64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Hits {
+    type Item = f64;
+    type IntoIter = std::iter::Take<std::array::IntoIter<f64, MAX_HITS>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lambdas.into_iter().take(self.len)
+    }
+}
+
+impl fmt::Debug for Hits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Allocation-free version of `Shape::intersects`, for the hot loops.
+/// It returns the same lambdas, but sorted and stored in place. It is implemented for the shapes with at most
+/// `Hits::CAPACITY` intersections (not for a `Scene`, whose number of intersections is unbounded).
+pub trait HitQuery {
+    /// Returns the intersections of the shape with a line, sorted by lambda.
+    fn hits(&self, line: &Line3) -> Hits;
+}
+
+impl HitQuery for Sphere {
+    fn hits(&self, line: &Line3) -> Hits {
+        let mut hits: Hits = Hits::new();
+        let o: Vector3 = line.a - self.c;
+        let b: f64 = line.v.dot(&o) * 2.0;
+        let c: f64 = o.x.powi(2) + o.y.powi(2) + o.z.powi(2) - self.r.powi(2);
+        let discrim: f64 = b.powi(2) - 4. * line.qa * c;
+        if discrim > 0. {
+            let discrim2: f64 = discrim.sqrt();
+            hits.lambdas = [(-b - discrim2) / (2. * line.qa), (-b + discrim2) / (2. * line.qa)];
+            hits.len = 2;
+        } else if discrim == 0. {
+            hits.push((-b) / (2. * line.qa));
+        }
+        hits
+    }
+}
+
+impl HitQuery for Plane {
+    fn hits(&self, line: &Line3) -> Hits {
+        let mut hits: Hits = Hits::new();
+        let denom: f64 = self.n.dot(&line.v);
+        if denom != 0. {
+            hits.push((-self.n.dot(&line.a) - self.d) / denom);
+        }
+        hits
+    }
+}
+
+impl HitQuery for Triangle3 {
+    fn hits(&self, line: &Line3) -> Hits {
+        let mut hits: Hits = self.plane.hits(line);
+        if let Some(&lambda) = hits.as_slice().first() {
+            let b: Vector3 = self.barycentric(&line.calc_point(lambda));
+            if !(1.0 >= b.x && b.x >= 0.0 && 1.0 >= b.y && b.y >= 0.0 && 1.0 >= b.z && b.z >= 0.0) {
+                hits.len = 0;
+            }
+        }
+        hits
+    }
+}
+
+impl HitQuery for Polygon3 {
+    fn hits(&self, line: &Line3) -> Hits {
+        let mut hits: Hits = Hits::new();
+        if let Some(lambda) = self.line_intersection(line) {
+            hits.push(lambda);
+        }
+        hits
+    }
+}
+
+impl HitQuery for Tetrahedron {
+    fn hits(&self, line: &Line3) -> Hits {
+        let mut hits: Hits = Hits::new();
+        if let Some([entry, exit]) = self.entry_exit(line) {
+            hits.push(entry.0);
+            if exit.0 != entry.0 {
+                hits.push(exit.0);
+            }
+        }
+        hits
+    }
+}
+
+impl HitQuery for Aabb {
+    fn hits(&self, line: &Line3) -> Hits {
+        let mut hits: Hits = Hits::new();
+        if let Some((entry, exit)) = self.entry_exit(line) {
+            hits.push(entry);
+            if exit != entry {
+                hits.push(exit);
+            }
+        }
+        hits
+    }
+}
+
+impl HitQuery for ShapeKind {
+    fn hits(&self, line: &Line3) -> Hits {
+        dispatch!(self, shape => shape.hits(line))
+    }
+}
+
+impl<S: HitQuery> HitQuery for Transformed<S> {
+    /// The lambdas are valid in world space (like with `Shape::intersects`).
+    fn hits(&self, line: &Line3) -> Hits {
+        self.shape.hits(&self.to_object(line))
+    }
+}
@@ -0,0 +1,209 @@
+use crate::aabb::Aabb;
+use crate::hits::{Hits, HitQuery};
+use crate::line3::Line3;
+use crate::obb::Obb;
+use crate::sphere::Sphere;
+use crate::tetrahedron::Tetrahedron;
+use crate::transformed::Transformed;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+// Distance along the line (relative to the size of the mesh) to consider two crossings with a mesh the same one
+const MERGE_TOLERANCE: f64 = 1e-9;
+
+/// Part of a line inside a closed shape: from the lambda where the line enters the shape to the lambda where it
+/// exits (always `entry <= exit`, whatever the direction of the line).
+/// Both normals are the outward normals (normalized) of the surface, so the exit normal points along the line.
+#[derive(Clone, Copy, Debug)]
+pub struct Interval {
+    pub entry: f64,            // Lambda where the line enters the shape
+    pub exit: f64,             // Lambda where the line exits the shape
+    pub entry_normal: Vector3, // Outward normal of the surface at the entry point
+    pub exit_normal: Vector3,  // Outward normal of the surface at the exit point
+}
+
+impl Interval {
+    /// Creates a new `Interval`.
+    pub fn new(entry: f64, exit: f64, entry_normal: &Vector3, exit_normal: &Vector3) -> Interval {
+        Self { entry, exit, entry_normal: *entry_normal, exit_normal: *exit_normal }
+    }
+
+    /// Returns the length of the interval, in lambda units (multiply by the magnitude of the director vector of the
+    /// line to get a distance).
+    pub fn length(&self) -> f64 {
+        self.exit - self.entry
+    }
+
+    /// Returns true if the lambda is inside the interval.
+    pub fn contains(&self, lambda: f64) -> bool {
+        self.entry <= lambda && lambda <= self.exit
+    }
+}
+
+/// Closed shapes (with an inside) that can tell which parts of a line are inside them.
+pub trait Intervals {
+    /// Returns the intervals of a (whole) line inside the shape, sorted by lambda and without overlaps.
+    /// Convex shapes return one interval at most. A line that just touches the surface has no interval.
+    fn intervals(&self, line: &Line3) -> Vec<Interval>;
+}
+
+impl Intervals for Sphere {
+    fn intervals(&self, line: &Line3) -> Vec<Interval> {
+        let hits: Hits = self.hits(line);
+        match *hits.as_slice() {
+            [entry, exit] => {
+                let entry_normal: Vector3 = (line.calc_point(entry) - self.c) / self.r;
+                let exit_normal: Vector3 = (line.calc_point(exit) - self.c) / self.r;
+                vec![Interval::new(entry, exit, &entry_normal, &exit_normal)]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+// Slab method in the axes of a box, with the origin of the line and its director vector in those axes.
+// Returns the entry and exit lambdas, with the outward normals in those axes (as (axis, sign)).
+fn slab_interval(a: [f64; 3], v: [f64; 3], min: [f64; 3], max: [f64; 3]) -> Option<[(f64, usize, f64); 2]> {
+    let mut entry: (f64, usize, f64) = (f64::NEG_INFINITY, 0, -1.0);
+    let mut exit: (f64, usize, f64) = (f64::INFINITY, 0, 1.0);
+    for axis in 0..3 {
+        if v[axis] == 0. {
+            if a[axis] < min[axis] || a[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        // Moving forward along the axis, the line enters through the min face and exits through the max one
+        let sign: f64 = if v[axis] > 0. { 1.0 } else { -1.0 };
+        let (near, far) = if v[axis] > 0. { (min[axis], max[axis]) } else { (max[axis], min[axis]) };
+        let l1: f64 = (near - a[axis]) / v[axis];
+        let l2: f64 = (far - a[axis]) / v[axis];
+        if l1 > entry.0 {
+            entry = (l1, axis, -sign);
+        }
+        if l2 < exit.0 {
+            exit = (l2, axis, sign);
+        }
+    }
+
+    if entry.0 >= exit.0 {
+        return None;
+    }
+    Some([entry, exit])
+}
+
+impl Intervals for Aabb {
+    fn intervals(&self, line: &Line3) -> Vec<Interval> {
+        let axes: [Vector3; 3] = [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)];
+        let slabs = slab_interval(
+            [line.a.x, line.a.y, line.a.z],
+            [line.v.x, line.v.y, line.v.z],
+            [self.min.x, self.min.y, self.min.z],
+            [self.max.x, self.max.y, self.max.z],
+        );
+        match slabs {
+            Some([entry, exit]) => vec![Interval::new(
+                entry.0, exit.0, &(axes[entry.1] * entry.2), &(axes[exit.1] * exit.2),
+            )],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Intervals for Obb {
+    fn intervals(&self, line: &Line3) -> Vec<Interval> {
+        let a: Vector3 = self.to_local(&line.a);
+        let h: Vector3 = self.half_extents;
+        let slabs = slab_interval(
+            [a.x, a.y, a.z],
+            [line.v.dot(&self.axes[0]), line.v.dot(&self.axes[1]), line.v.dot(&self.axes[2])],
+            [-h.x, -h.y, -h.z],
+            [h.x, h.y, h.z],
+        );
+        match slabs {
+            Some([entry, exit]) => vec![Interval::new(
+                entry.0, exit.0, &(self.axes[entry.1] * entry.2), &(self.axes[exit.1] * exit.2),
+            )],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Intervals for Tetrahedron {
+    fn intervals(&self, line: &Line3) -> Vec<Interval> {
+        match self.entry_exit(line) {
+            Some([entry, exit]) if entry.0 < exit.0 => vec![Interval::new(
+                entry.0, exit.0, &self.face_normal(entry.1), &self.face_normal(exit.1),
+            )],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl<S: Intervals> Intervals for Transformed<S> {
+    /// The lambdas are valid in world space, and the normals are transformed like in `Shape::normal`.
+    fn intervals(&self, line: &Line3) -> Vec<Interval> {
+        self.shape.intervals(&self.to_object(line)).iter()
+            .map(|i| Interval::new(
+                i.entry,
+                i.exit,
+                &self.transform.transform_normal(&i.entry_normal),
+                &self.transform.transform_normal(&i.exit_normal),
+            ))
+            .collect()
+    }
+}
+
+/// Returns the intervals of a line inside a closed mesh of triangles (anticlockwise, like in `mesh_volume`), sorted
+/// by lambda.
+/// The line enters the mesh through the triangles facing it, and exits through the ones facing away. A line through
+/// an edge (or a vertex) hits all the triangles that share it, so the crossings in the same direction closer than a
+/// tolerance (relative to the size of the mesh) are merged, and counted once.
+pub fn mesh_intervals(triangles: &[Triangle3], line: &Line3) -> Vec<Interval> {
+    let Some(first) = triangles.first() else {
+        return Vec::new();
+    };
+    let size: f64 = triangles.iter().flat_map(|t| [t.a, t.b, t.c]).map(|p| p.distance_to(first.a)).fold(0.0, f64::max);
+    let eps: f64 = MERGE_TOLERANCE * size / line.v.magnitude();
+
+    // Crossings of the line with the surface: (lambda, 1 entering or -1 exiting, index of the triangle)
+    let mut crossings: Vec<(f64, i32, usize)> = Vec::new();
+    for (i, t) in triangles.iter().enumerate() {
+        let facing: f64 = t.plane.n.dot(&line.v);
+        if facing == 0. {
+            continue;
+        }
+        for lambda in t.hits(line) {
+            crossings.push((lambda, if facing < 0. { 1 } else { -1 }, i));
+        }
+    }
+    // At the same lambda, entries first (so touching solids are merged)
+    crossings.sort_by(|c1, c2| c1.0.total_cmp(&c2.0).then(c2.1.cmp(&c1.1)));
+    let mut merged: Vec<(f64, i32, usize)> = Vec::new();
+    for crossing in crossings {
+        let repeated: bool = merged.iter().rev()
+            .take_while(|c| crossing.0 - c.0 <= eps)
+            .any(|c| c.1 == crossing.1);
+        if !repeated {
+            merged.push(crossing);
+        }
+    }
+
+    let mut intervals: Vec<Interval> = Vec::new();
+    let mut depth: i32 = 0;
+    let mut entry: (f64, usize) = (0.0, 0);
+    for (lambda, step, i) in merged {
+        if step > 0 {
+            if depth == 0 {
+                entry = (lambda, i);
+            }
+            depth += 1;
+        } else if depth > 0 {
+            depth -= 1;
+            if depth == 0 && lambda > entry.0 {
+                intervals.push(Interval::new(entry.0, lambda, &triangles[entry.1].plane.n, &triangles[i].plane.n));
+            }
+        }
+    }
+    intervals
+}
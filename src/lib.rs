@@ -3,10 +3,61 @@ mod line3;
 mod sphere;
 mod plane;
 mod triangle3;
+mod tetrahedron;
+mod polygon3;
+mod triangulation;
+mod circle3;
+mod aabb;
+mod overlap;
+mod capsule;
+mod convex_hull;
+mod gjk;
+mod obb;
+mod contact;
+mod sweep;
+mod optics;
+mod sampling;
+mod mass;
+mod transform;
+mod quaternion;
+mod transformed;
+mod motion;
+mod scene;
+mod shape_kind;
+mod hits;
+mod interval;
+mod csg;
+mod torus;
+mod sdf;
 mod test;
 
-pub use line3::Line3;
+pub use line3::{Line3, Approach};
 pub use sphere::Sphere;
-pub use plane::Plane;
-pub use triangle3::Triangle3;
-pub use base::{Shape, Intersection};
\ No newline at end of file
+pub use plane::{Plane, PlaneIntersection};
+pub use triangle3::{Triangle3, TriangleIntersection};
+pub use tetrahedron::Tetrahedron;
+pub use polygon3::Polygon3;
+pub use triangulation::triangulate;
+pub use circle3::Circle3;
+pub use aabb::Aabb;
+pub use overlap::Overlaps;
+pub use capsule::Capsule;
+pub use convex_hull::ConvexHull;
+pub use gjk::{gjk_distance, gjk_overlaps, epa_penetration, Separation, Penetration};
+pub use obb::Obb;
+pub use contact::{Contact, Contacts};
+pub use sweep::{SweepHit, Sweeps};
+pub use optics::{fresnel_schlick, fresnel_dielectric};
+pub use mass::{mesh_area, mesh_volume, mesh_centroid, mesh_inertia};
+pub use transform::{Transform, Transformable};
+pub use quaternion::Quaternion;
+pub use transformed::Transformed;
+pub use motion::{MovingShape, MovingSphere, Keyframe, Keyframed};
+pub use scene::{Scene, SceneShape};
+pub use shape_kind::ShapeKind;
+pub use hits::{Hits, HitQuery};
+pub use interval::{Interval, Intervals, mesh_intervals};
+pub use csg::{Csg, CsgOp};
+pub use torus::Torus;
+pub use sdf::{Sdf, SmoothCsg, Repeat, SphereTraced};
+pub use base::{Shape, Intersection, ClosestPoint, Support, SurfaceSample, Bounded};
\ No newline at end of file
@@ -1,6 +1,10 @@
+use crate::base::ClosestPoint;
 use vector3::Vector3;
 use std::fmt;
 
+// Tolerance for the sine^2 of the angle between two lines, to consider them parallel
+const PARALLEL_TOLERANCE: f64 = 1e-12;
+
 /// Struct that define a line in 3D space, with the pivot point and the director vector of the line
 #[derive(Clone, Copy)]
 pub struct Line3 {
@@ -33,6 +37,105 @@ impl Line3 {
         let ap: Vector3 = *p - self.a;
         ap.cross(&self.v).magnitude() / self.qa.sqrt()
     }
+
+    /// Calculates the lambda of the point of the line closest to a given point (its orthogonal projection)
+    pub fn project(&self, p: &Vector3) -> f64 {
+        (*p - self.a).dot(&self.v) / self.qa
+    }
+}
+
+/// Result of the closest approach between two lines (or segments, or rays).
+#[derive(Clone, Copy, Debug)]
+pub struct Approach {
+    pub lambda1: f64,   // Lambda of the closest point in the first line
+    pub lambda2: f64,   // Lambda of the closest point in the second line
+    pub p1: Vector3,    // Closest point in the first line
+    pub p2: Vector3,    // Closest point in the second line
+    pub dist: f64,      // Distance between both lines (the distance between p1 and p2)
+    pub parallel: bool, // The lines are parallel, so the closest points are not unique
+}
+
+impl Line3 {
+    /// Calculates the closest approach between two (infinite) lines.
+    /// If the lines are parallel, any pair of points at the same height would do: the pivot point of `self` and
+    /// its projection in `other` are returned, and the `parallel` flag is set.
+    pub fn closest_approach(&self, other: &Line3) -> Approach {
+        let all: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+        self.closest_approach_within(other, all, all)
+    }
+
+    /// Calculates the closest approach between two segments, defined by the lines from `a` (lambda 0) to `a + v`
+    /// (lambda 1), as they were created with `Line3::new(a, b)`.
+    pub fn closest_approach_segment(&self, other: &Line3) -> Approach {
+        self.closest_approach_within(other, (0.0, 1.0), (0.0, 1.0))
+    }
+
+    /// Calculates the closest approach between two rays, that start at `a` and go in the direction of `v`
+    /// (positive lambdas).
+    pub fn closest_approach_ray(&self, other: &Line3) -> Approach {
+        let positive: (f64, f64) = (0.0, f64::INFINITY);
+        self.closest_approach_within(other, positive, positive)
+    }
+
+    /// Calculates the closest approach between two lines, limiting the lambdas of each one to a range.
+    /// # Arguments
+    /// * `other` - The second line.
+    /// * `range1` - Minimum and maximum lambda allowed for `self` (they can be infinite).
+    /// * `range2` - Minimum and maximum lambda allowed for `other` (they can be infinite).
+    /// # Returns
+    /// An `Approach` with the lambdas and points of the closest approach, and the distance between them.
+    /// If the lines are parallel, the closest points are not unique: one of the pairs is returned, with the
+    /// `parallel` flag set.
+    pub fn closest_approach_within(&self, other: &Line3, range1: (f64, f64), range2: (f64, f64)) -> Approach {
+        let r: Vector3 = self.a - other.a;
+        let b: f64 = self.v.dot(&other.v);
+        let c: f64 = self.v.dot(&r);
+        let f: f64 = other.v.dot(&r);
+
+        // qa*qa' - b^2 = |v x v'|^2, that is zero for parallel lines
+        let denom: f64 = self.qa * other.qa - b * b;
+        let parallel: bool = denom <= PARALLEL_TOLERANCE * self.qa * other.qa;
+
+        let mut lambda1: f64 = if parallel {
+            0.0_f64.clamp(range1.0, range1.1)
+        } else {
+            ((b * f - c * other.qa) / denom).clamp(range1.0, range1.1)
+        };
+
+        // Closest point of the other line to the point of self, and if it is out of range, the other way round
+        let mut lambda2: f64 = (b * lambda1 + f) / other.qa;
+        if lambda2 < range2.0 || lambda2 > range2.1 {
+            lambda2 = lambda2.clamp(range2.0, range2.1);
+            lambda1 = ((b * lambda2 - c) / self.qa).clamp(range1.0, range1.1);
+        }
+
+        let p1: Vector3 = self.calc_point(lambda1);
+        let p2: Vector3 = other.calc_point(lambda2);
+        Approach { lambda1, lambda2, p1, p2, dist: p1.distance_to(p2), parallel }
+    }
+}
+
+impl ClosestPoint for Line3 {
+    /// Returns the point of the line closest to a given point (its orthogonal projection)
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        self.calc_point(self.project(p))
+    }
+
+    /// Calculates the distance between a point and the line
+    fn dist_point(&self, p: &Vector3) -> f64 {
+        Line3::dist_point(self, p)
+    }
+}
+
+/// Returns the point of the segment (a, b) closest to the point p.
+pub(crate) fn closest_point_segment(a: &Vector3, b: &Vector3, p: &Vector3) -> Vector3 {
+    let ab: Vector3 = *b - *a;
+    let qa: f64 = ab.dot(&ab);
+    if qa == 0. {
+        return *a;
+    }
+    let lambda: f64 = ((*p - *a).dot(&ab) / qa).clamp(0.0, 1.0);
+    *a + ab * lambda
 }
 
 impl fmt::Debug for Line3 {
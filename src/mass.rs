@@ -0,0 +1,245 @@
+use crate::aabb::Aabb;
+use crate::capsule::Capsule;
+use crate::obb::Obb;
+use crate::sphere::Sphere;
+use crate::tetrahedron::Tetrahedron;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+use std::f64::consts::PI;
+
+// Mass properties of solids with uniform density. The inertia tensors are calculated for a given mass, relative to
+// the centroid of the solid and in the world axes, as a 3x3 matrix (row by row).
+
+impl Sphere {
+    /// Returns the area of the surface of the sphere.
+    pub fn area(&self) -> f64 {
+        4.0 * PI * self.r * self.r
+    }
+
+    /// Returns the volume of the sphere.
+    pub fn volume(&self) -> f64 {
+        4.0 / 3.0 * PI * self.r.powi(3)
+    }
+
+    /// Returns the inertia tensor of the solid sphere with the given mass, relative to its center.
+    pub fn inertia(&self, mass: f64) -> [[f64; 3]; 3] {
+        let i: f64 = 2.0 / 5.0 * mass * self.r * self.r;
+        [[i, 0.0, 0.0], [0.0, i, 0.0], [0.0, 0.0, i]]
+    }
+}
+
+impl Triangle3 {
+    /// Returns the area of the triangle.
+    pub fn area(&self) -> f64 {
+        (self.b - self.a).cross(&(self.c - self.a)).magnitude() / 2.0
+    }
+
+    /// Returns the centroid of the triangle.
+    pub fn centroid(&self) -> Vector3 {
+        (self.a + self.b + self.c) / 3.0
+    }
+}
+
+impl Aabb {
+    /// Returns the area of the surface of the box.
+    pub fn area(&self) -> f64 {
+        box_area(&(self.half_extents() * 2.0))
+    }
+
+    /// Returns the volume of the box.
+    pub fn volume(&self) -> f64 {
+        let size: Vector3 = self.half_extents() * 2.0;
+        size.x * size.y * size.z
+    }
+
+    /// Returns the inertia tensor of the solid box with the given mass, relative to its center.
+    pub fn inertia(&self, mass: f64) -> [[f64; 3]; 3] {
+        let d: Vector3 = box_inertia(&(self.half_extents() * 2.0), mass);
+        [[d.x, 0.0, 0.0], [0.0, d.y, 0.0], [0.0, 0.0, d.z]]
+    }
+}
+
+impl Obb {
+    /// Returns the area of the surface of the box.
+    pub fn area(&self) -> f64 {
+        box_area(&(self.half_extents * 2.0))
+    }
+
+    /// Returns the volume of the box.
+    pub fn volume(&self) -> f64 {
+        let size: Vector3 = self.half_extents * 2.0;
+        size.x * size.y * size.z
+    }
+
+    /// Returns the inertia tensor of the solid box with the given mass, relative to its center.
+    pub fn inertia(&self, mass: f64) -> [[f64; 3]; 3] {
+        // R * diagonal * R^T, with the local axes as the columns of R
+        let d: Vector3 = box_inertia(&(self.half_extents * 2.0), mass);
+        let diagonal: [f64; 3] = [d.x, d.y, d.z];
+        let component = |axis: &Vector3, i: usize| -> f64 { [axis.x, axis.y, axis.z][i] };
+        let mut inertia: [[f64; 3]; 3] = [[0.0; 3]; 3];
+        for (i, row) in inertia.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| component(&self.axes[k], i) * diagonal[k] * component(&self.axes[k], j)).sum();
+            }
+        }
+        inertia
+    }
+}
+
+impl Tetrahedron {
+    /// Returns the area of the surface of the tetrahedron.
+    pub fn area(&self) -> f64 {
+        (0..4).map(|face| {
+            let [a, b, c] = self.face(face);
+            (b - a).cross(&(c - a)).magnitude() / 2.0
+        }).sum()
+    }
+
+    /// Returns the centroid of the tetrahedron.
+    pub fn centroid(&self) -> Vector3 {
+        (self.a + self.b + self.c + self.d) / 4.0
+    }
+
+    /// Returns the inertia tensor of the solid tetrahedron with the given mass, relative to its centroid.
+    pub fn inertia(&self, mass: f64) -> [[f64; 3]; 3] {
+        // Second moment relative to the vertex a (the sign of the volume doesn´t matter, as it is divided back)
+        let (ab, ac, ad) = (self.b - self.a, self.c - self.a, self.d - self.a);
+        let vol6: f64 = ab.dot(&ac.cross(&ad));
+        let second: [[f64; 3]; 3] = tetrahedron_second_moment(&ab, &ac, &ad, vol6);
+        let centroid: Vector3 = (ab + ac + ad) / 4.0;
+        inertia_from_second_moment(&second, vol6 / 6.0, &centroid, mass)
+    }
+}
+
+impl Capsule {
+    /// Returns the area of the surface of the capsule.
+    pub fn area(&self) -> f64 {
+        2.0 * PI * self.r * self.a.distance_to(self.b) + 4.0 * PI * self.r * self.r
+    }
+
+    /// Returns the volume of the capsule.
+    pub fn volume(&self) -> f64 {
+        PI * self.r * self.r * self.a.distance_to(self.b) + 4.0 / 3.0 * PI * self.r.powi(3)
+    }
+
+    /// Returns the inertia tensor of the solid capsule with the given mass, relative to its center.
+    pub fn inertia(&self, mass: f64) -> [[f64; 3]; 3] {
+        // Cylinder plus the two hemispheres (whose centroids are at 3r/8 of the ends of the cylinder), with the mass
+        // split by volume
+        let (r, h) = (self.r, self.a.distance_to(self.b));
+        let cylinder: f64 = mass * PI * r * r * h / self.volume();
+        let spheres: f64 = mass - cylinder;
+        let axial: f64 = cylinder * r * r / 2.0 + spheres * 2.0 / 5.0 * r * r;
+        let transverse: f64 = cylinder * (r * r / 4.0 + h * h / 12.0) +
+            spheres * (2.0 / 5.0 * r * r + h * h / 4.0 + 3.0 / 8.0 * h * r);
+
+        // transverse * Id + (axial - transverse) * axis axis^T (a capsule without length is a sphere)
+        let axis: Vector3 = if h > 0. { (self.b - self.a) / h } else { Vector3::new(0.0, 0.0, 0.0) };
+        let d: [f64; 3] = [axis.x, axis.y, axis.z];
+        let mut inertia: [[f64; 3]; 3] = [[0.0; 3]; 3];
+        for (i, row) in inertia.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                let identity: f64 = if i == j { transverse } else { 0.0 };
+                *value = identity + (axial - transverse) * d[i] * d[j];
+            }
+        }
+        inertia
+    }
+}
+
+/// Returns the area of a mesh of triangles.
+pub fn mesh_area(triangles: &[Triangle3]) -> f64 {
+    triangles.iter().map(Triangle3::area).sum()
+}
+
+/// Returns the volume enclosed by a closed mesh of triangles (divergence theorem).
+/// The triangles must be in anticlockwise order looked from outside the mesh (with their normals pointing
+/// outwards): otherwise the volume is negative.
+pub fn mesh_volume(triangles: &[Triangle3]) -> f64 {
+    // Sum of the signed volumes of the tetrahedrons formed by each triangle and the origin
+    triangles.iter().map(|t| t.a.dot(&t.b.cross(&t.c))).sum::<f64>() / 6.0
+}
+
+/// Returns the centroid of the solid enclosed by a closed mesh of triangles (anticlockwise, like in `mesh_volume`).
+/// The mesh must enclose a volume (it can´t be empty or flat).
+pub fn mesh_centroid(triangles: &[Triangle3]) -> Vector3 {
+    let mut moment: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+    let mut vol6: f64 = 0.0;
+    for t in triangles {
+        let det: f64 = t.a.dot(&t.b.cross(&t.c));
+        moment = moment + (t.a + t.b + t.c) * det;
+        vol6 += det;
+    }
+    if vol6 == 0. {
+        panic!("The mesh must enclose a volume");
+    }
+    // The centroid of each tetrahedron is (a + b + c + origin) / 4
+    moment / (vol6 * 4.0)
+}
+
+/// Returns the inertia tensor of the solid enclosed by a closed mesh of triangles (anticlockwise, like in
+/// `mesh_volume`), with the given mass, relative to its centroid.
+/// The mesh must enclose a volume (it can´t be empty or flat).
+pub fn mesh_inertia(triangles: &[Triangle3], mass: f64) -> [[f64; 3]; 3] {
+    let mut second: [[f64; 3]; 3] = [[0.0; 3]; 3];
+    let mut vol6: f64 = 0.0;
+    for t in triangles {
+        let det: f64 = t.a.dot(&t.b.cross(&t.c));
+        let tetrahedron: [[f64; 3]; 3] = tetrahedron_second_moment(&t.a, &t.b, &t.c, det);
+        for i in 0..3 {
+            for j in 0..3 {
+                second[i][j] += tetrahedron[i][j];
+            }
+        }
+        vol6 += det;
+    }
+    inertia_from_second_moment(&second, vol6 / 6.0, &mesh_centroid(triangles), mass)
+}
+
+fn box_area(size: &Vector3) -> f64 {
+    2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+}
+
+// Diagonal of the inertia tensor of a solid box, in its local axes
+fn box_inertia(size: &Vector3, mass: f64) -> Vector3 {
+    let (x2, y2, z2) = (size.x * size.x, size.y * size.y, size.z * size.z);
+    Vector3::new(y2 + z2, x2 + z2, x2 + y2) * (mass / 12.0)
+}
+
+// Second moment of volume (integral of p * p^T) of the tetrahedron with vertexes in the origin, a, b and c,
+// given six times its signed volume: det / 120 * (a a^T + b b^T + c c^T + s s^T), with s = a + b + c
+fn tetrahedron_second_moment(a: &Vector3, b: &Vector3, c: &Vector3, det: f64) -> [[f64; 3]; 3] {
+    let s: Vector3 = *a + *b + *c;
+    let vectors: [[f64; 3]; 4] = [[a.x, a.y, a.z], [b.x, b.y, b.z], [c.x, c.y, c.z], [s.x, s.y, s.z]];
+    let mut second: [[f64; 3]; 3] = [[0.0; 3]; 3];
+    for (i, row) in second.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = vectors.iter().map(|v| v[i] * v[j]).sum::<f64>() * det / 120.0;
+        }
+    }
+    second
+}
+
+// Inertia tensor relative to the centroid, given the second moment of volume relative to the origin, the (signed)
+// volume and the centroid: I = density * (trace(C) * Id - C), with C moved to the centroid (parallel axis theorem)
+fn inertia_from_second_moment(second: &[[f64; 3]; 3], volume: f64, centroid: &Vector3, mass: f64) -> [[f64; 3]; 3] {
+    let c: [f64; 3] = [centroid.x, centroid.y, centroid.z];
+    let mut moved: [[f64; 3]; 3] = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            moved[i][j] = second[i][j] - volume * c[i] * c[j];
+        }
+    }
+    let trace: f64 = moved[0][0] + moved[1][1] + moved[2][2];
+    let density: f64 = mass / volume;
+    let mut inertia: [[f64; 3]; 3] = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            let identity: f64 = if i == j { trace } else { 0.0 };
+            inertia[i][j] = density * (identity - moved[i][j]);
+        }
+    }
+    inertia
+}
@@ -0,0 +1,175 @@
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, Bounded};
+use crate::line3::Line3;
+use crate::quaternion::Quaternion;
+use crate::sphere::Sphere;
+use crate::transform::{Transform, Transformable};
+use list::List;
+use vector3::Vector3;
+
+/// Shapes that move along the time (for motion blur).
+/// The queries are like the ones of `Shape`, with the time of the line: the shape is evaluated at that time.
+pub trait MovingShape {
+    /// Returns the normal (normalized) of the shape at a given point, at the given time.
+    fn normal_at(&self, point: &Vector3, time: f64) -> Vector3;
+
+    /// Returns the intersections of the shape with a line, at the given time.
+    fn intersects_at(&self, line: &Line3, time: f64) -> List<f64>;
+
+    /// Returns the closest positive intersection of the shape with a line, at the given time.
+    fn closest_intersection_at(&self, line: &Line3, time: f64) -> Option<Intersection>;
+}
+
+/// Sphere whose center moves linearly from `c0` (at time `t0`) to `c1` (at time `t1`).
+/// Out of that interval, the sphere stays at the nearest end.
+#[derive(Clone, Copy)]
+pub struct MovingSphere {
+    pub c0: Vector3, // Center of the sphere at time t0
+    pub c1: Vector3, // Center of the sphere at time t1
+    pub t0: f64,     // Start of the motion
+    pub t1: f64,     // End of the motion
+    pub r: f64,      // Radius of the sphere
+}
+
+impl MovingSphere {
+    /// Creates a new `MovingSphere`, given its center at the start and at the end of the motion, the times of both,
+    /// and the radius.
+    pub fn new(c0: &Vector3, c1: &Vector3, t0: f64, t1: f64, r: f64) -> MovingSphere {
+        if t1 <= t0 {
+            panic!("The end of the motion must be after its start");
+        }
+        Self { c0: *c0, c1: *c1, t0, t1, r }
+    }
+
+    /// Returns the sphere at the given time.
+    pub fn at(&self, time: f64) -> Sphere {
+        let f: f64 = ((time - self.t0) / (self.t1 - self.t0)).clamp(0.0, 1.0);
+        Sphere::new(&(self.c0 + (self.c1 - self.c0) * f), self.r)
+    }
+}
+
+impl MovingShape for MovingSphere {
+    fn normal_at(&self, point: &Vector3, time: f64) -> Vector3 {
+        self.at(time).normal(point)
+    }
+
+    fn intersects_at(&self, line: &Line3, time: f64) -> List<f64> {
+        self.at(time).intersects(line)
+    }
+
+    fn closest_intersection_at(&self, line: &Line3, time: f64) -> Option<Intersection> {
+        self.at(time).closest_intersection(line)
+    }
+}
+
+impl Bounded for MovingSphere {
+    /// Returns the box that contains the sphere during the whole motion.
+    fn bounding_box(&self) -> Aabb {
+        Aabb::new(&self.c0, &self.c1).expand(self.r)
+    }
+}
+
+/// Pose of a shape at a given time: scaled first, then rotated and last translated.
+#[derive(Clone, Copy)]
+pub struct Keyframe {
+    pub time: f64,
+    pub translation: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+impl Keyframe {
+    /// Creates a new `Keyframe`.
+    pub fn new(time: f64, translation: &Vector3, rotation: &Quaternion, scale: &Vector3) -> Keyframe {
+        Self { time, translation: *translation, rotation: *rotation, scale: *scale }
+    }
+
+    /// Returns the object to world transform of the pose.
+    pub fn transform(&self) -> Transform {
+        Transform::scale(&self.scale)
+            .then(&Transform::from(self.rotation))
+            .then(&Transform::translation(&self.translation))
+    }
+
+    // Pose between two keyframes: linear interpolation of the translation and the scale, and slerp of the rotation
+    fn interpolate(&self, next: &Keyframe, time: f64) -> Keyframe {
+        let f: f64 = (time - self.time) / (next.time - self.time);
+        Keyframe::new(
+            time,
+            &(self.translation + (next.translation - self.translation) * f),
+            &self.rotation.slerp(&next.rotation, f),
+            &(self.scale + (next.scale - self.scale) * f),
+        )
+    }
+}
+
+/// Any shape moved along the time with keyframed poses.
+/// Between keyframes the pose is interpolated, and out of them the shape stays at the nearest one.
+#[derive(Clone)]
+pub struct Keyframed<S> {
+    pub shape: S,             // Shape, in its own (object) space
+    keyframes: Vec<Keyframe>, // Poses of the shape, sorted by time
+}
+
+impl<S> Keyframed<S> {
+    /// Creates a new `Keyframed` shape, given the shape (in object space) and its poses (at least one, in any order).
+    pub fn new(shape: S, keyframes: &[Keyframe]) -> Keyframed<S> {
+        if keyframes.is_empty() {
+            panic!("The motion needs at least one keyframe");
+        }
+        let mut keyframes: Vec<Keyframe> = keyframes.to_vec();
+        keyframes.sort_by(|k1, k2| k1.time.total_cmp(&k2.time));
+        Self { shape, keyframes }
+    }
+
+    /// Returns the keyframes, sorted by time.
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// Returns the object to world transform of the shape at the given time.
+    pub fn transform_at(&self, time: f64) -> Transform {
+        let next: usize = self.keyframes.partition_point(|k| k.time <= time);
+        if next == 0 {
+            return self.keyframes[0].transform();
+        }
+        if next == self.keyframes.len() {
+            return self.keyframes[next - 1].transform();
+        }
+        self.keyframes[next - 1].interpolate(&self.keyframes[next], time).transform()
+    }
+}
+
+impl<S: Shape> MovingShape for Keyframed<S> {
+    fn normal_at(&self, point: &Vector3, time: f64) -> Vector3 {
+        let t: Transform = self.transform_at(time);
+        let local: Vector3 = t.inverse().transform_point(point);
+        t.transform_normal(&self.shape.normal(&local))
+    }
+
+    /// The lambdas are valid in world space (like with `Transformed`).
+    fn intersects_at(&self, line: &Line3, time: f64) -> List<f64> {
+        self.shape.intersects(&line.transform(&self.transform_at(time).inverse()))
+    }
+
+    /// The lambdas are valid in world space (like with `Transformed`).
+    fn closest_intersection_at(&self, line: &Line3, time: f64) -> Option<Intersection> {
+        self.shape.closest_intersection(&line.transform(&self.transform_at(time).inverse()))
+    }
+}
+
+impl<S: Bounded> Bounded for Keyframed<S> {
+    /// Returns a box that contains the shape during the whole motion.
+    /// The rotations between keyframes make the exact box expensive, so it is conservative: every point of the
+    /// shape is within `max scale * (distance from the origin to the farthest point of its box)` of the
+    /// translation, which moves linearly between keyframes.
+    fn bounding_box(&self) -> Aabb {
+        let local: Aabb = self.shape.bounding_box();
+        let reach: f64 = local.center().magnitude() + local.half_extents().magnitude();
+        let scale: f64 = self.keyframes.iter()
+            .map(|k| k.scale.x.abs().max(k.scale.y.abs()).max(k.scale.z.abs()))
+            .fold(0.0, f64::max);
+        let translations: Vec<Vector3> = self.keyframes.iter().map(|k| k.translation).collect();
+        Aabb::from_points(&translations).expand(reach * scale)
+    }
+}
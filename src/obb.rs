@@ -0,0 +1,150 @@
+use crate::aabb::Aabb;
+use crate::base::{ClosestPoint, Support, Bounded};
+use vector3::Vector3;
+
+use std::fmt;
+
+// Tolerance to check that the axes of the box are perpendicular
+const ORTHOGONALITY_TOLERANCE: f64 = 1e-9;
+
+/// Oriented box in 3D space.
+#[derive(Clone, Copy)]
+pub struct Obb {
+    pub c: Vector3,             // Center of the box
+    pub axes: [Vector3; 3],     // Local axes of the box (already normalized, right handed)
+    pub half_extents: Vector3,  // Half of the size of the box along each local axis
+}
+
+impl Obb {
+    /// Creates a new `Obb`.
+    /// # Arguments
+    /// * `c` - A reference to the center of the box.
+    /// * `x_axis` - A reference to the direction of the first local axis of the box.
+    /// * `y_axis` - A reference to the direction of the second local axis (perpendicular to the first one).
+    /// * `half_extents` - A reference to the half of the size of the box along each local axis.
+    ///
+    /// The third local axis is `x_axis x y_axis`.
+    pub fn new(c: &Vector3, x_axis: &Vector3, y_axis: &Vector3, half_extents: &Vector3) -> Obb {
+        if x_axis.is_zero() || y_axis.is_zero() {
+            panic!("The axes of the box cannot be zero");
+        }
+        let x: Vector3 = x_axis.normalize();
+        let y: Vector3 = y_axis.normalize();
+        if x.dot(&y).abs() > ORTHOGONALITY_TOLERANCE {
+            panic!("The axes of the box must be perpendicular");
+        }
+        if half_extents.x < 0. || half_extents.y < 0. || half_extents.z < 0. {
+            panic!("The size of the box cannot be negative");
+        }
+        Self { c: *c, axes: [x, y, x.cross(&y)], half_extents: *half_extents }
+    }
+
+    /// Returns the coordinates of a point in the local axes of the box (relative to its center).
+    pub fn to_local(&self, p: &Vector3) -> Vector3 {
+        let cp: Vector3 = *p - self.c;
+        Vector3::new(cp.dot(&self.axes[0]), cp.dot(&self.axes[1]), cp.dot(&self.axes[2]))
+    }
+
+    /// Returns the point given its coordinates in the local axes of the box.
+    pub fn from_local(&self, local: &Vector3) -> Vector3 {
+        self.c + self.axes[0] * local.x + self.axes[1] * local.y + self.axes[2] * local.z
+    }
+
+    /// Returns the eight vertexes of the box.
+    pub fn vertices(&self) -> [Vector3; 8] {
+        let h: Vector3 = self.half_extents;
+        [
+            self.from_local(&Vector3::new(-h.x, -h.y, -h.z)),
+            self.from_local(&Vector3::new(h.x, -h.y, -h.z)),
+            self.from_local(&Vector3::new(-h.x, h.y, -h.z)),
+            self.from_local(&Vector3::new(h.x, h.y, -h.z)),
+            self.from_local(&Vector3::new(-h.x, -h.y, h.z)),
+            self.from_local(&Vector3::new(h.x, -h.y, h.z)),
+            self.from_local(&Vector3::new(-h.x, h.y, h.z)),
+            self.from_local(&Vector3::new(h.x, h.y, h.z)),
+        ]
+    }
+
+    /// Returns true if the point is inside the box (or in its surface).
+    pub fn contains(&self, p: &Vector3) -> bool {
+        let local: Vector3 = self.to_local(p);
+        let h: Vector3 = self.half_extents;
+        local.x.abs() <= h.x && local.y.abs() <= h.y && local.z.abs() <= h.z
+    }
+
+    /// Returns the point of the box (including its interior) closest to a given point.
+    /// If the point is inside the box, it is returned itself.
+    pub fn clamp(&self, p: &Vector3) -> Vector3 {
+        let local: Vector3 = self.to_local(p);
+        let h: Vector3 = self.half_extents;
+        self.from_local(&Vector3::new(local.x.clamp(-h.x, h.x), local.y.clamp(-h.y, h.y), local.z.clamp(-h.z, h.z)))
+    }
+
+    /// Returns the signed distance between a point and the surface of the box.
+    /// It is positive outside the box, and negative inside.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        let local: Vector3 = self.to_local(p);
+        let h: Vector3 = self.half_extents;
+        let q: Vector3 = Vector3::new(local.x.abs() - h.x, local.y.abs() - h.y, local.z.abs() - h.z);
+        if q.x <= 0. && q.y <= 0. && q.z <= 0. {
+            q.x.max(q.y).max(q.z)
+        } else {
+            Vector3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).magnitude()
+        }
+    }
+}
+
+impl From<Aabb> for Obb {
+    fn from(aabb: Aabb) -> Obb {
+        Self {
+            c: aabb.center(),
+            axes: [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)],
+            half_extents: aabb.half_extents(),
+        }
+    }
+}
+
+impl ClosestPoint for Obb {
+    /// Returns the point of the surface of the box closest to a given point.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        if !self.contains(p) {
+            return self.clamp(p);
+        }
+        // Inside the box: project on the closest face
+        let l: Vector3 = self.to_local(p);
+        let mut local: [f64; 3] = [l.x, l.y, l.z];
+        let h: [f64; 3] = [self.half_extents.x, self.half_extents.y, self.half_extents.z];
+        let axis: usize = (0..3).min_by(|&i, &j| (h[i] - local[i].abs()).total_cmp(&(h[j] - local[j].abs()))).unwrap();
+        local[axis] = if local[axis] >= 0. { h[axis] } else { -h[axis] };
+        self.from_local(&Vector3::new(local[0], local[1], local[2]))
+    }
+}
+
+impl Support for Obb {
+    /// Returns the vertex of the box farthest in a given direction.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        let h: [f64; 3] = [self.half_extents.x, self.half_extents.y, self.half_extents.z];
+        (0..3).fold(self.c, |p, i| {
+            let sign: f64 = if self.axes[i].dot(dir) >= 0. { 1.0 } else { -1.0 };
+            p + self.axes[i] * (sign * h[i])
+        })
+    }
+}
+
+impl Bounded for Obb {
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&self.vertices())
+    }
+}
+
+impl fmt::Display for Obb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x, y, z] = self.axes;
+        write!(
+            f,
+            "C ({}, {}, {}) X ({}, {}, {}) Y ({}, {}, {}) Z ({}, {}, {}) Half extents ({}, {}, {})",
+            self.c.x, self.c.y, self.c.z, x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z,
+            self.half_extents.x, self.half_extents.y, self.half_extents.z
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use crate::base::{Shape, Intersection};
+use crate::line3::Line3;
+use vector3::Vector3;
+
+impl Line3 {
+    /// Reflects the line about the surface of a shape at one of its intersections.
+    /// # Arguments
+    /// * `shape` - A reference to the shape hit by the line.
+    /// * `intersection` - A reference to the intersection (as returned by `closest_intersection`).
+    /// # Returns
+    /// The reflected line, starting at the hit point, with a director vector of the same length as the original one.
+    pub fn reflect<S: Shape + ?Sized>(&self, shape: &S, intersection: &Intersection) -> Line3 {
+        let point: Vector3 = self.calc_point(intersection.lambda);
+        self.reflect_at(&point, &shape.normal(&point))
+    }
+
+    /// Reflects the line about a surface, given the hit point and the normal of the surface there.
+    /// The side the normal points to doesn´t matter.
+    pub fn reflect_at(&self, point: &Vector3, n: &Vector3) -> Line3 {
+        let n: Vector3 = n.normalize();
+        let reflected: Vector3 = self.v - n * (2.0 * self.v.dot(&n));
+        Line3::new(point, &(*point + reflected))
+    }
+
+    /// Refracts the line through the surface of a shape at one of its intersections (Snell´s law).
+    /// # Arguments
+    /// * `shape` - A reference to the shape hit by the line.
+    /// * `intersection` - A reference to the intersection (as returned by `closest_intersection`).
+    /// * `eta` - Ratio of the refractive indexes: the one the line comes from divided by the one it enters.
+    /// # Returns
+    /// The refracted line, starting at the hit point, with a director vector of the same length as the original one.
+    /// If there is total internal reflection, it returns Option None.
+    pub fn refract<S: Shape + ?Sized>(&self, shape: &S, intersection: &Intersection, eta: f64) -> Option<Line3> {
+        let point: Vector3 = self.calc_point(intersection.lambda);
+        self.refract_at(&point, &shape.normal(&point), eta)
+    }
+
+    /// Refracts the line through a surface, given the hit point and the normal of the surface there.
+    /// The side the normal points to doesn´t matter: `eta` always goes from the side the line comes from.
+    /// If there is total internal reflection, it returns Option None.
+    pub fn refract_at(&self, point: &Vector3, n: &Vector3, eta: f64) -> Option<Line3> {
+        let length: f64 = self.qa.sqrt();
+        let v: Vector3 = self.v / length;
+        // Normal against the line
+        let mut n: Vector3 = n.normalize();
+        let mut cos_i: f64 = -v.dot(&n);
+        if cos_i < 0. {
+            n = n * -1.0;
+            cos_i = -cos_i;
+        }
+
+        let sin2_t: f64 = eta * eta * (1.0 - cos_i * cos_i);
+        if sin2_t > 1. {
+            return None;
+        }
+        let cos_t: f64 = (1.0 - sin2_t).sqrt();
+        let refracted: Vector3 = v * eta + n * (eta * cos_i - cos_t);
+        Some(Line3::new(point, &(*point + refracted * length)))
+    }
+}
+
+/// Schlick´s approximation of the Fresnel reflectance.
+/// # Arguments
+/// * `cos_i` - Cosine of the angle between the incident line and the normal.
+/// * `eta1` - Refractive index of the medium the line comes from.
+/// * `eta2` - Refractive index of the medium the line enters.
+/// # Returns
+/// The fraction of the light that is reflected (between 0 and 1).
+/// With total internal reflection it returns 1.
+pub fn fresnel_schlick(cos_i: f64, eta1: f64, eta2: f64) -> f64 {
+    let mut cos: f64 = cos_i.abs().min(1.0);
+    if eta1 > eta2 {
+        // Going to a less dense medium, the approximation uses the transmitted angle
+        let sin2_t: f64 = (eta1 / eta2).powi(2) * (1.0 - cos * cos);
+        if sin2_t > 1. {
+            return 1.0;
+        }
+        cos = (1.0 - sin2_t).sqrt();
+    }
+    let r0: f64 = ((eta1 - eta2) / (eta1 + eta2)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+}
+
+/// Exact Fresnel reflectance of a dielectric surface, for unpolarized light.
+/// # Arguments
+/// * `cos_i` - Cosine of the angle between the incident line and the normal.
+/// * `eta1` - Refractive index of the medium the line comes from.
+/// * `eta2` - Refractive index of the medium the line enters.
+/// # Returns
+/// The fraction of the light that is reflected (between 0 and 1): the average of the reflectances of the s and p
+/// polarizations. With total internal reflection it returns 1.
+pub fn fresnel_dielectric(cos_i: f64, eta1: f64, eta2: f64) -> f64 {
+    let cos_i: f64 = cos_i.abs().min(1.0);
+    let sin2_t: f64 = (eta1 / eta2).powi(2) * (1.0 - cos_i * cos_i);
+    if sin2_t > 1. {
+        return 1.0;
+    }
+    let cos_t: f64 = (1.0 - sin2_t).sqrt();
+    let rs: f64 = (eta1 * cos_i - eta2 * cos_t) / (eta1 * cos_i + eta2 * cos_t);
+    let rp: f64 = (eta2 * cos_i - eta1 * cos_t) / (eta2 * cos_i + eta1 * cos_t);
+    (rs * rs + rp * rp) / 2.0
+}
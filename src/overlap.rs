@@ -0,0 +1,263 @@
+use crate::aabb::Aabb;
+use crate::base::ClosestPoint;
+use crate::plane::{Plane, PlaneIntersection};
+use crate::polygon3::Polygon3;
+use crate::sphere::Sphere;
+use crate::tetrahedron::Tetrahedron;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+// Tolerance (relative to the size of the shapes) to consider that two shapes touch
+const TOLERANCE: f64 = 1e-10;
+
+/// Boolean overlap test between two shapes.
+/// Shapes that just touch are considered to overlap. Shapes with volume (spheres, boxes and tetrahedrons) are
+/// solid, so a shape fully inside them overlaps them too.
+pub trait Overlaps<T> {
+    /// Returns true if both shapes overlap (or touch).
+    fn overlaps(&self, other: &T) -> bool;
+}
+
+// Convex polytope, described with the data needed by the separating axis test
+struct Polytope {
+    vertices: Vec<Vector3>,
+    normals: Vec<Vector3>, // Normals of the faces (and, for flat polytopes, of the edges inside their plane)
+    edges: Vec<Vector3>,   // Directions of the edges
+}
+
+impl Polytope {
+    fn from_triangle(triangle: &Triangle3) -> Polytope {
+        let n: Vector3 = triangle.plane.n;
+        let edges: Vec<Vector3> = vec![triangle.b - triangle.a, triangle.c - triangle.b, triangle.a - triangle.c];
+        let mut normals: Vec<Vector3> = edges.iter().map(|e| n.cross(e)).collect();
+        normals.push(n);
+        Polytope { vertices: vec![triangle.a, triangle.b, triangle.c], normals, edges }
+    }
+
+    fn from_aabb(aabb: &Aabb) -> Polytope {
+        let axes: Vec<Vector3> = vec![
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+        Polytope { vertices: aabb.vertices().to_vec(), normals: axes.clone(), edges: axes }
+    }
+
+    fn from_tetrahedron(tetrahedron: &Tetrahedron) -> Polytope {
+        let (a, b, c, d) = (tetrahedron.a, tetrahedron.b, tetrahedron.c, tetrahedron.d);
+        Polytope {
+            vertices: vec![a, b, c, d],
+            normals: (0..4).map(|face| tetrahedron.face_normal(face)).collect(),
+            edges: vec![b - a, c - a, d - a, c - b, d - b, d - c],
+        }
+    }
+
+    // Polygons can be concave, so they are split in (convex) triangles, reusing the triangulation of the polygon.
+    // A polygon too thin to be triangulated is tested as its border (a segment for each edge), so that it isn´t
+    // reported as not overlapping anything.
+    fn from_polygon(polygon: &Polygon3) -> Vec<Polytope> {
+        if polygon.triangles().is_empty() {
+            let n: Vector3 = polygon.plane.n;
+            let vertices: &[Vector3] = &polygon.vertices;
+            return (0..vertices.len()).map(|i| {
+                let (a, b) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+                Polytope { vertices: vec![a, b], normals: vec![n, n.cross(&(b - a))], edges: vec![b - a] }
+            }).collect();
+        }
+        polygon.triangles().iter().map(Polytope::from_triangle).collect()
+    }
+
+    // Separating axis test: convex polytopes don´t overlap if and only if there is an axis (a face normal of any of
+    // them, or the cross product of two of their edges) where their projections don´t overlap
+    fn overlaps(&self, other: &Polytope) -> bool {
+        let size: f64 = self.vertices.iter().chain(other.vertices.iter())
+            .map(|p| p.distance_to(self.vertices[0]))
+            .fold(0.0, f64::max);
+
+        let separates = |axis: &Vector3| -> bool {
+            let (min1, max1) = project(&self.vertices, axis);
+            let (min2, max2) = project(&other.vertices, axis);
+            let eps: f64 = TOLERANCE * size * axis.magnitude();
+            max1 < min2 - eps || max2 < min1 - eps
+        };
+
+        if self.normals.iter().chain(other.normals.iter()).any(separates) {
+            return false;
+        }
+        for e1 in self.edges.iter() {
+            for e2 in other.edges.iter() {
+                let axis: Vector3 = e1.cross(e2);
+                // Parallel edges don´t define a new axis
+                if axis.magnitude() > TOLERANCE * e1.magnitude() * e2.magnitude() && separates(&axis) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn overlaps_plane(&self, plane: &Plane) -> bool {
+        let (min, max) = project(&self.vertices, &plane.n);
+        min <= -plane.d && max >= -plane.d
+    }
+}
+
+// Minimum and maximum of the projections of the points over the axis
+fn project(points: &[Vector3], axis: &Vector3) -> (f64, f64) {
+    points.iter().map(|p| p.dot(axis)).fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), d| (min.min(d), max.max(d)))
+}
+
+fn any_overlap(polytopes1: &[Polytope], polytopes2: &[Polytope]) -> bool {
+    polytopes1.iter().any(|p1| polytopes2.iter().any(|p2| p1.overlaps(p2)))
+}
+
+impl Overlaps<Sphere> for Sphere {
+    fn overlaps(&self, other: &Sphere) -> bool {
+        self.c.distance_to(other.c) <= self.r + other.r
+    }
+}
+
+impl Overlaps<Plane> for Sphere {
+    fn overlaps(&self, other: &Plane) -> bool {
+        other.dist_point(&self.c) <= self.r
+    }
+}
+
+impl Overlaps<Triangle3> for Sphere {
+    fn overlaps(&self, other: &Triangle3) -> bool {
+        other.dist_point(&self.c) <= self.r
+    }
+}
+
+impl Overlaps<Aabb> for Sphere {
+    fn overlaps(&self, other: &Aabb) -> bool {
+        other.clamp(&self.c).distance_to(self.c) <= self.r
+    }
+}
+
+impl Overlaps<Tetrahedron> for Sphere {
+    fn overlaps(&self, other: &Tetrahedron) -> bool {
+        other.contains(&self.c) || other.dist_point(&self.c) <= self.r
+    }
+}
+
+impl Overlaps<Polygon3> for Sphere {
+    fn overlaps(&self, other: &Polygon3) -> bool {
+        other.dist_point(&self.c) <= self.r
+    }
+}
+
+impl Overlaps<Plane> for Plane {
+    /// Planes overlap unless they are parallel (and not coincident).
+    fn overlaps(&self, other: &Plane) -> bool {
+        !matches!(self.intersect_plane(other), PlaneIntersection::Parallel)
+    }
+}
+
+impl Overlaps<Triangle3> for Plane {
+    fn overlaps(&self, other: &Triangle3) -> bool {
+        Polytope::from_triangle(other).overlaps_plane(self)
+    }
+}
+
+impl Overlaps<Aabb> for Plane {
+    fn overlaps(&self, other: &Aabb) -> bool {
+        Polytope::from_aabb(other).overlaps_plane(self)
+    }
+}
+
+impl Overlaps<Tetrahedron> for Plane {
+    fn overlaps(&self, other: &Tetrahedron) -> bool {
+        Polytope::from_tetrahedron(other).overlaps_plane(self)
+    }
+}
+
+impl Overlaps<Polygon3> for Plane {
+    fn overlaps(&self, other: &Polygon3) -> bool {
+        let (min, max) = project(&other.vertices, &self.n);
+        min <= -self.d && max >= -self.d
+    }
+}
+
+impl Overlaps<Triangle3> for Triangle3 {
+    fn overlaps(&self, other: &Triangle3) -> bool {
+        Polytope::from_triangle(self).overlaps(&Polytope::from_triangle(other))
+    }
+}
+
+impl Overlaps<Aabb> for Triangle3 {
+    fn overlaps(&self, other: &Aabb) -> bool {
+        Polytope::from_triangle(self).overlaps(&Polytope::from_aabb(other))
+    }
+}
+
+impl Overlaps<Tetrahedron> for Triangle3 {
+    fn overlaps(&self, other: &Tetrahedron) -> bool {
+        Polytope::from_triangle(self).overlaps(&Polytope::from_tetrahedron(other))
+    }
+}
+
+impl Overlaps<Polygon3> for Triangle3 {
+    fn overlaps(&self, other: &Polygon3) -> bool {
+        any_overlap(&[Polytope::from_triangle(self)], &Polytope::from_polygon(other))
+    }
+}
+
+impl Overlaps<Aabb> for Aabb {
+    fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x &&
+        self.min.y <= other.max.y && self.max.y >= other.min.y &&
+        self.min.z <= other.max.z && self.max.z >= other.min.z
+    }
+}
+
+impl Overlaps<Tetrahedron> for Aabb {
+    fn overlaps(&self, other: &Tetrahedron) -> bool {
+        Polytope::from_aabb(self).overlaps(&Polytope::from_tetrahedron(other))
+    }
+}
+
+impl Overlaps<Polygon3> for Aabb {
+    fn overlaps(&self, other: &Polygon3) -> bool {
+        any_overlap(&[Polytope::from_aabb(self)], &Polytope::from_polygon(other))
+    }
+}
+
+impl Overlaps<Tetrahedron> for Tetrahedron {
+    fn overlaps(&self, other: &Tetrahedron) -> bool {
+        Polytope::from_tetrahedron(self).overlaps(&Polytope::from_tetrahedron(other))
+    }
+}
+
+impl Overlaps<Polygon3> for Tetrahedron {
+    fn overlaps(&self, other: &Polygon3) -> bool {
+        any_overlap(&[Polytope::from_tetrahedron(self)], &Polytope::from_polygon(other))
+    }
+}
+
+impl Overlaps<Polygon3> for Polygon3 {
+    fn overlaps(&self, other: &Polygon3) -> bool {
+        any_overlap(&Polytope::from_polygon(self), &Polytope::from_polygon(other))
+    }
+}
+
+// The overlap test is symmetric, so the reversed pairs just swap the arguments
+macro_rules! reversed_overlaps {
+    ($($a:ty => $b:ty),* $(,)?) => {
+        $(
+            impl Overlaps<$a> for $b {
+                fn overlaps(&self, other: &$a) -> bool {
+                    other.overlaps(self)
+                }
+            }
+        )*
+    };
+}
+
+reversed_overlaps!(
+    Sphere => Plane, Sphere => Triangle3, Sphere => Aabb, Sphere => Tetrahedron, Sphere => Polygon3,
+    Plane => Triangle3, Plane => Aabb, Plane => Tetrahedron, Plane => Polygon3,
+    Triangle3 => Aabb, Triangle3 => Tetrahedron, Triangle3 => Polygon3,
+    Aabb => Tetrahedron, Aabb => Polygon3,
+    Tetrahedron => Polygon3,
+);
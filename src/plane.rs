@@ -1,9 +1,25 @@
-use crate::base::{Shape, Intersection};
+use crate::base::{Shape, Intersection, ClosestPoint};
 use crate::line3::Line3;
 use list::List;
 use std::fmt;
 use vector3::Vector3;
 
+// Tolerance for the sine^2 of the angle between two planes (or for the triple product of three normals),
+// to consider them parallel
+const PARALLEL_TOLERANCE: f64 = 1e-12;
+
+// Tolerance for the distance between two parallel planes (relative to their distance to the origin), to consider them
+// coincident
+const COINCIDENT_TOLERANCE: f64 = 1e-9;
+
+/// Result of the intersection of two planes.
+#[derive(Clone, Copy, Debug)]
+pub enum PlaneIntersection {
+    Line(Line3), // The planes intersect in a line
+    Parallel,    // The planes are parallel, so they don´t intersect
+    Coincident,  // The planes are the same one
+}
+
 /// A plane in 3D space.
 #[derive(Clone, Copy)]
 pub struct Plane {
@@ -20,14 +36,74 @@ impl Plane {
         if n.is_zero() {
             panic!("The normal of the plane cannot be zero");
         }
+        let n: Vector3 = n.normalize();
         Self {
             a: *a,
-            n: n.normalize(),
-            d: -n.dot(&a),
+            n,
+            d: -n.dot(a),
+        }
+    }
+
+    /// Returns the signed distance between a point and the plane.
+    /// It is positive if the point is in the side the normal points to, and negative in the other side.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        self.n.dot(p) + self.d
+    }
+}
+
+impl Plane {
+    /// Calculates the intersection of two planes.
+    /// # Returns
+    /// * `PlaneIntersection::Line` with the intersection line if the planes are not parallel. The director vector of
+    ///   the line is normalized, and its pivot point is the point of the line closest to the origin.
+    /// * `PlaneIntersection::Parallel` if the planes are parallel (or nearly parallel, so the intersection would be
+    ///   numerically meaningless).
+    /// * `PlaneIntersection::Coincident` if both are the same plane (with any orientation of the normal).
+    pub fn intersect_plane(&self, other: &Plane) -> PlaneIntersection {
+        let u: Vector3 = self.n.cross(&other.n);
+        let qu: f64 = u.dot(&u);
+
+        // The normals are normalized, so |u|^2 is the sine^2 of the angle between the planes
+        if qu <= PARALLEL_TOLERANCE {
+            let d2: f64 = if self.n.dot(&other.n) > 0. { other.d } else { -other.d };
+            let scale: f64 = 1.0_f64.max(self.d.abs()).max(other.d.abs());
+            if (self.d - d2).abs() <= COINCIDENT_TOLERANCE * scale {
+                return PlaneIntersection::Coincident;
+            }
+            return PlaneIntersection::Parallel;
+        }
+
+        // Point that fulfills both plane equations (n · x = -d), in the plane generated by both normals
+        let a: Vector3 = (other.n.cross(&u) * -self.d + u.cross(&self.n) * -other.d) / qu;
+        PlaneIntersection::Line(Line3::new(&a, &(a + u.normalize())))
+    }
+
+    /// Calculates the intersection point of three planes.
+    /// If any pair of the planes is parallel (or nearly parallel), or the three of them intersect in the same line,
+    /// there is no single intersection point, and it returns Option None.
+    pub fn intersect_planes(&self, p2: &Plane, p3: &Plane) -> Option<Vector3> {
+        let n23: Vector3 = p2.n.cross(&p3.n);
+        let det: f64 = self.n.dot(&n23);
+        if det.powi(2) <= PARALLEL_TOLERANCE {
+            return None;
         }
+
+        // Cramer's rule for n_i · x = -d_i
+        let point: Vector3 = (n23 * -self.d + p3.n.cross(&self.n) * -p2.d + self.n.cross(&p2.n) * -p3.d) / det;
+        Some(point)
     }
+}
 
+impl ClosestPoint for Plane {
+    /// Returns the point of the plane closest to a given point (its orthogonal projection)
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        *p - self.n * self.signed_dist_point(p)
+    }
 
+    /// Returns the (unsigned) distance between a point and the plane
+    fn dist_point(&self, p: &Vector3) -> f64 {
+        self.signed_dist_point(p).abs()
+    }
 }
 
 impl Shape for Plane {
@@ -63,7 +139,7 @@ impl Shape for Plane {
             let num: f64 = -self.n.dot(&line.a) - self.d;
             return Some(Intersection::new(num / denom, None));
         }
-        return None;
+        None
     }
 }
 
@@ -0,0 +1,246 @@
+use vector3::Vector3;
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, ClosestPoint, Support, Bounded, farthest_point};
+use crate::plane::Plane;
+use crate::line3::{Line3, closest_point_segment};
+use crate::triangle3::Triangle3;
+use crate::triangulation::triangulate;
+use list::List;
+
+use std::sync::OnceLock;
+
+// Maximum distance allowed between a vertex and the plane of the polygon, relative to the size of the polygon.
+const PLANARITY_TOLERANCE: f64 = 1e-9;
+
+// Struct that define a planar polygon in 3D space, with the vertex defined in an
+// anticlockwise order. The polygon can be concave, but it must not self-intersect.
+#[derive(Clone)]
+pub struct Polygon3 {
+    pub vertices: Vec<Vector3>, // Vertexes of the polygon, in anticlockwise order
+
+    pub plane: Plane, // Plane that contains the polygon
+
+    area: f64, // Factors precalculated to speed up point in polygon and area calculus
+    u: usize,  // Axes of the 2D projection used by the point in polygon test
+    v: usize,
+
+    triangles: OnceLock<Vec<Triangle3>>, // Triangulation of the polygon, calculated the first time it is needed
+    cumulative_areas: OnceLock<Vec<f64>>, // Sum of the areas of the triangles up to each one (included), for sampling
+}
+
+impl Polygon3 {
+    /// Creates a new `Polygon3` from its vertexes.
+    /// # Arguments
+    /// * `vertices` - The vertexes of the polygon (at least 3), all of them in the same plane.
+    ///
+    /// The vertexes must be defined in anticlockwise order (looked from the visible side), and the
+    /// closing edge (from the last vertex to the first) is implicit.
+    /// # Returns
+    /// A new `Polygon3` with the given vertices.
+    pub fn new(vertices: &[Vector3]) -> Polygon3 {
+        if vertices.len() < 3 {
+            panic!("The polygon needs at least three vertices.");
+        }
+
+        let newell: Vector3 = newell_normal(vertices);
+        let area: f64 = newell.magnitude() / 2.0;
+        if area == 0. {
+            panic!("The polygon cannot be defined by aligned points.");
+        }
+
+        let plane: Plane = Plane::new(&vertices[0], &newell.normalize());
+        let size: f64 = vertices.iter().map(|p| p.distance_to(vertices[0])).fold(0.0, f64::max);
+        if vertices.iter().any(|p| (p.dot(&plane.n) + plane.d).abs() > PLANARITY_TOLERANCE * size) {
+            panic!("The vertices of the polygon must be coplanar.");
+        }
+
+        // Project over the coordinate plane where the polygon is "biggest" (dropping the dominant axis of the normal)
+        let n: Vector3 = plane.n;
+        let (u, v) = if n.x.abs() >= n.y.abs() && n.x.abs() >= n.z.abs() {
+            (1, 2)
+        } else if n.y.abs() >= n.z.abs() {
+            (2, 0)
+        } else {
+            (0, 1)
+        };
+
+        Self {
+            vertices: vertices.to_vec(), plane, area, u, v,
+            triangles: OnceLock::new(), cumulative_areas: OnceLock::new(),
+        }
+    }
+
+    /// Returns the area of the polygon.
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+
+    /// Returns the triangles covering the polygon, all of them defined in anticlockwise order (so their normals match
+    /// the polygon's one).
+    /// They are calculated with `triangulate` the first time they are needed. A polygon thinner than the tolerance of
+    /// `triangulate` has no triangles.
+    pub fn triangles(&self) -> &[Triangle3] {
+        self.triangles.get_or_init(|| triangulate(&self.vertices, &[]))
+    }
+
+    // Sum of the areas of the triangles up to each one (included)
+    pub(crate) fn cumulative_areas(&self) -> &[f64] {
+        self.cumulative_areas.get_or_init(|| {
+            self.triangles().iter().scan(0.0, |sum, triangle| {
+                *sum += triangle.area();
+                Some(*sum)
+            }).collect()
+        })
+    }
+
+    /// Returns the centroid (center of mass of the surface) of the polygon.
+    pub fn centroid(&self) -> Vector3 {
+        // Fan of triangles from the first vertex, weighted with their signed areas (so it works for concave polygons)
+        let origin: Vector3 = self.vertices[0];
+        let mut centroid: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+        for i in 1..self.vertices.len() - 1 {
+            let p: Vector3 = self.vertices[i];
+            let q: Vector3 = self.vertices[i + 1];
+            let signed_area: f64 = (p - origin).cross(&(q - origin)).dot(&self.plane.n) / 2.0;
+            centroid = centroid + (origin + p + q) * (signed_area / 3.0);
+        }
+        centroid / self.area
+    }
+
+    /// Returns true if the point is inside the polygon (or in its border).
+    /// The point is supposed to be in the plane of the polygon: if it isn´t, its projection on the plane is tested.
+    pub fn contains(&self, p: &Vector3) -> bool {
+        let px: f64 = coord(p, self.u);
+        let py: f64 = coord(p, self.v);
+
+        // Winding number test, that is valid for concave polygons
+        let mut winding: i32 = 0;
+        for (i, a) in self.vertices.iter().enumerate() {
+            let b: &Vector3 = &self.vertices[(i + 1) % self.vertices.len()];
+            let (ax, ay) = (coord(a, self.u), coord(a, self.v));
+            let (bx, by) = (coord(b, self.u), coord(b, self.v));
+
+            // Which side of the edge is the point on (positive means left)
+            let side: f64 = (bx - ax) * (py - ay) - (px - ax) * (by - ay);
+            if side == 0. && px >= ax.min(bx) && px <= ax.max(bx) && py >= ay.min(by) && py <= ay.max(by) {
+                // The point is in the border
+                return true;
+            }
+            if ay <= py {
+                if by > py && side > 0. {
+                    winding += 1;
+                }
+            } else if by <= py && side < 0. {
+                winding -= 1;
+            }
+        }
+        winding != 0
+    }
+
+    /// Returns the signed distance between a point and the polygon.
+    /// It is positive if the point is in the visible side of the polygon (where the normal points to), and
+    /// negative in the other side.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        let dist: f64 = self.dist_point(p);
+        if self.plane.signed_dist_point(p) < 0. { -dist } else { dist }
+    }
+
+    // Lambda of the intersection of the line with the polygon, be it positive or negative
+    pub(crate) fn line_intersection(&self, line: &Line3) -> Option<f64> {
+        let intersection: Intersection = self.plane.closest_intersection(line)?;
+        if self.contains(&line.calc_point(intersection.lambda)) {
+            Some(intersection.lambda)
+        } else {
+            None
+        }
+    }
+}
+
+/// Newell's method: robust normal for any planar polygon, even concave, with a magnitude of twice its area.
+pub(crate) fn newell_normal(vertices: &[Vector3]) -> Vector3 {
+    let mut newell: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+    for (i, p) in vertices.iter().enumerate() {
+        let q: &Vector3 = &vertices[(i + 1) % vertices.len()];
+        newell = newell + p.cross(q);
+    }
+    newell
+}
+
+impl ClosestPoint for Polygon3 {
+    /// Returns the point of the polygon closest to a given point.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        // If the projection on the plane is inside the polygon, that is the closest point. If not, it is in the border.
+        let projection: Vector3 = self.plane.closest_point(p);
+        if self.contains(&projection) {
+            return projection;
+        }
+
+        let mut closest: Vector3 = self.vertices[0];
+        let mut min_dist: f64 = f64::INFINITY;
+        for (i, a) in self.vertices.iter().enumerate() {
+            let b: &Vector3 = &self.vertices[(i + 1) % self.vertices.len()];
+            let q: Vector3 = closest_point_segment(a, b, p);
+            let dist: f64 = q.distance_to(*p);
+            if dist < min_dist {
+                min_dist = dist;
+                closest = q;
+            }
+        }
+        closest
+    }
+}
+
+fn coord(p: &Vector3, axis: usize) -> f64 {
+    match axis {
+        0 => p.x,
+        1 => p.y,
+        _ => p.z,
+    }
+}
+
+impl Support for Polygon3 {
+    /// Returns the vertex of the polygon farthest in a given direction.
+    /// For concave polygons, the support is the one of their convex hull.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        farthest_point(&self.vertices, dir)
+    }
+}
+
+impl Bounded for Polygon3 {
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&self.vertices)
+    }
+}
+
+impl Shape for Polygon3 {
+    /// Returns the normal (normalized) of the polygon at a given point.
+    fn normal(&self, _point: &Vector3) -> Vector3 {
+        self.plane.n
+    }
+
+    /// Returns the intersection of the polygon with a line.
+    /// If the line is parallel to the polygon, it returns an empty list.
+    /// If the line is in the plane of the polygon, it returns an empty list as a convention (because really, all lambdas fulfill).
+    /// If the line intersects the polygon, it returns a list with the lambda value.
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        match self.line_intersection(line) {
+            Some(lambda) => {
+                let mut intersections: List<f64> = List::<f64>::new();
+                intersections.push(lambda);
+                intersections
+            }
+            None => List::<f64>::new(),
+        }
+    }
+
+    /// Returns the intersection of the polygon with a line, if it is in the positive direction of the line.
+    /// If the line is parallel to the polygon, or doesn´t cross it, it returns Option None.
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        let lambda: f64 = self.line_intersection(line)?;
+        if lambda > 0. {
+            Some(Intersection::new(lambda, None))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use vector3::Vector3;
+
+use std::fmt;
+
+// Above this cosine of the angle between two rotations, slerp falls back to nlerp to avoid dividing by ~0
+const SLERP_THRESHOLD: f64 = 0.9995;
+
+/// Quaternion `w + x i + y j + z k`, used to represent rotations in 3D space (when normalized).
+#[derive(Clone, Copy)]
+pub struct Quaternion {
+    pub w: f64, // Real (scalar) part
+    pub x: f64, // Imaginary (vector) part
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    /// Creates a new `Quaternion` from its components.
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Quaternion {
+        Self { w, x, y, z }
+    }
+
+    /// Creates the identity `Quaternion` (no rotation).
+    pub fn identity() -> Quaternion {
+        Self { w: 1.0, x: 0.0, y: 0.0, z: 0.0 }
+    }
+
+    /// Creates the rotation `Quaternion` given the axis of rotation and the angle (in radians, anticlockwise around
+    /// the axis).
+    pub fn from_axis_angle(axis: &Vector3, angle: f64) -> Quaternion {
+        if axis.is_zero() {
+            panic!("The axis of the rotation cannot be zero");
+        }
+        let k: Vector3 = axis.normalize();
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        Self { w: cos, x: k.x * sin, y: k.y * sin, z: k.z * sin }
+    }
+
+    /// Returns the axis (normalized) and the angle (in radians, between 0 and 2 PI) of the rotation.
+    /// Without rotation, the axis is arbitrary (the X axis is returned).
+    pub fn to_axis_angle(&self) -> (Vector3, f64) {
+        let q: Quaternion = self.normalize();
+        let imaginary: Vector3 = Vector3::new(q.x, q.y, q.z);
+        let sin: f64 = imaginary.magnitude();
+        if sin == 0. {
+            return (Vector3::new(1.0, 0.0, 0.0), 0.0);
+        }
+        (imaginary / sin, 2.0 * sin.atan2(q.w))
+    }
+
+    /// Creates the rotation `Quaternion` from Euler angles (in radians): first `roll` around the X axis, then `pitch`
+    /// around the Y axis, and last `yaw` around the Z axis (all of them around the fixed world axes).
+    pub fn from_euler(roll: f64, pitch: f64, yaw: f64) -> Quaternion {
+        let (sr, cr) = (roll / 2.0).sin_cos();
+        let (sp, cp) = (pitch / 2.0).sin_cos();
+        let (sy, cy) = (yaw / 2.0).sin_cos();
+        Self {
+            w: cr * cp * cy + sr * sp * sy,
+            x: sr * cp * cy - cr * sp * sy,
+            y: cr * sp * cy + sr * cp * sy,
+            z: cr * cp * sy - sr * sp * cy,
+        }
+    }
+
+    /// Returns the Euler angles `(roll, pitch, yaw)` of the rotation, as defined in `from_euler`.
+    /// The pitch is between -PI/2 and PI/2, and the roll and the yaw between -PI and PI.
+    pub fn to_euler(&self) -> (f64, f64, f64) {
+        let q: Quaternion = self.normalize();
+        let roll: f64 = (2.0 * (q.w * q.x + q.y * q.z)).atan2(1.0 - 2.0 * (q.x * q.x + q.y * q.y));
+        let pitch: f64 = (2.0 * (q.w * q.y - q.z * q.x)).clamp(-1.0, 1.0).asin();
+        let yaw: f64 = (2.0 * (q.w * q.z + q.x * q.y)).atan2(1.0 - 2.0 * (q.y * q.y + q.z * q.z));
+        (roll, pitch, yaw)
+    }
+
+    /// Creates the rotation `Quaternion` from a rotation matrix (row by row, applied to column vectors).
+    pub fn from_matrix(m: &[[f64; 3]; 3]) -> Quaternion {
+        // Use the largest of w, x, y, z to divide by, for numerical stability
+        let trace: f64 = m[0][0] + m[1][1] + m[2][2];
+        let q: Quaternion = if trace > 0. {
+            let s: f64 = (trace + 1.0).sqrt() * 2.0;
+            Self::new(s / 4.0, (m[2][1] - m[1][2]) / s, (m[0][2] - m[2][0]) / s, (m[1][0] - m[0][1]) / s)
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s: f64 = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+            Self::new((m[2][1] - m[1][2]) / s, s / 4.0, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s)
+        } else if m[1][1] > m[2][2] {
+            let s: f64 = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+            Self::new((m[0][2] - m[2][0]) / s, (m[0][1] + m[1][0]) / s, s / 4.0, (m[1][2] + m[2][1]) / s)
+        } else {
+            let s: f64 = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+            Self::new((m[1][0] - m[0][1]) / s, (m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, s / 4.0)
+        };
+        q.normalize()
+    }
+
+    /// Returns the rotation matrix (row by row, applied to column vectors) of the quaternion.
+    pub fn to_matrix(&self) -> [[f64; 3]; 3] {
+        let Quaternion { w, x, y, z } = self.normalize();
+        [
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y)],
+        ]
+    }
+
+    /// Returns the magnitude (norm) of the quaternion.
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the quaternion normalized (magnitude 1).
+    pub fn normalize(&self) -> Quaternion {
+        let magnitude: f64 = self.magnitude();
+        if magnitude == 0. {
+            panic!("The zero quaternion cannot be normalized");
+        }
+        Self::new(self.w / magnitude, self.x / magnitude, self.y / magnitude, self.z / magnitude)
+    }
+
+    /// Returns the conjugate of the quaternion (the inverse rotation, for normalized quaternions).
+    pub fn conjugate(&self) -> Quaternion {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Returns the inverse of the quaternion.
+    pub fn inverse(&self) -> Quaternion {
+        let q2: f64 = self.dot(self);
+        if q2 == 0. {
+            panic!("The zero quaternion has no inverse");
+        }
+        let c: Quaternion = self.conjugate();
+        Self::new(c.w / q2, c.x / q2, c.y / q2, c.z / q2)
+    }
+
+    /// Returns the dot product of two quaternions (the cosine of half the angle between two rotations).
+    pub fn dot(&self, other: &Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the Hamilton product `self * other`: the rotation that applies `other` first, and then `self`.
+    pub fn multiply(&self, other: &Quaternion) -> Quaternion {
+        Self::new(
+            self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+            self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+        )
+    }
+
+    /// Returns the rotation that applies `self` first, and then `next` (like `Transform::then`).
+    pub fn then(&self, next: &Quaternion) -> Quaternion {
+        next.multiply(self)
+    }
+
+    /// Rotates a vector (the quaternion is normalized first).
+    pub fn rotate(&self, v: &Vector3) -> Vector3 {
+        let q: Quaternion = self.normalize();
+        // v + 2 w (u x v) + 2 u x (u x v), with u the imaginary part
+        let u: Vector3 = Vector3::new(q.x, q.y, q.z);
+        let t: Vector3 = u.cross(v) * 2.0;
+        *v + t * q.w + u.cross(&t)
+    }
+
+    /// Normalized linear interpolation between two rotations, through the shortest path.
+    /// `t` = 0 returns `self`, and `t` = 1 returns `other` (or its opposite, that is the same rotation).
+    pub fn nlerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+        let sign: f64 = if self.dot(other) < 0. { -1.0 } else { 1.0 };
+        Self::new(
+            self.w + (other.w * sign - self.w) * t,
+            self.x + (other.x * sign - self.x) * t,
+            self.y + (other.y * sign - self.y) * t,
+            self.z + (other.z * sign - self.z) * t,
+        ).normalize()
+    }
+
+    /// Spherical linear interpolation between two rotations (constant angular speed), through the shortest path.
+    /// `t` = 0 returns `self`, and `t` = 1 returns `other` (or its opposite, that is the same rotation).
+    pub fn slerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+        let q1: Quaternion = self.normalize();
+        let mut q2: Quaternion = other.normalize();
+        let mut cos: f64 = q1.dot(&q2);
+        if cos < 0. {
+            q2 = Self::new(-q2.w, -q2.x, -q2.y, -q2.z);
+            cos = -cos;
+        }
+        if cos > SLERP_THRESHOLD {
+            return q1.nlerp(&q2, t);
+        }
+        let theta: f64 = cos.acos();
+        let s1: f64 = ((1.0 - t) * theta).sin() / theta.sin();
+        let s2: f64 = (t * theta).sin() / theta.sin();
+        Self::new(q1.w * s1 + q2.w * s2, q1.x * s1 + q2.x * s2, q1.y * s1 + q2.y * s2, q1.z * s1 + q2.z * s2)
+    }
+}
+
+impl fmt::Display for Quaternion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}i + {}j + {}k", self.w, self.x, self.y, self.z)
+    }
+}
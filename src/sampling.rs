@@ -0,0 +1,111 @@
+use crate::base::{SurfaceSample, orthonormal_basis};
+use crate::circle3::Circle3;
+use crate::polygon3::Polygon3;
+use crate::sphere::Sphere;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+use std::f64::consts::PI;
+
+// The sampling functions take the random numbers from the caller (uniformly distributed in [0, 1)), so any random
+// generator (or low discrepancy sequence) can be used.
+
+impl Sphere {
+    /// Returns a point uniformly distributed in the surface of the sphere, given two random numbers in [0, 1).
+    /// The normal is the outward normal, and the pdf is per unit of area.
+    pub fn sample_surface(&self, u1: f64, u2: f64) -> SurfaceSample {
+        let z: f64 = 1.0 - 2.0 * u1;
+        let radius: f64 = (1.0 - z * z).max(0.0).sqrt();
+        let phi: f64 = 2.0 * PI * u2;
+        let normal: Vector3 = Vector3::new(radius * phi.cos(), radius * phi.sin(), z);
+        SurfaceSample::new(&(self.c + normal * self.r), &normal, 1.0 / (4.0 * PI * self.r * self.r))
+    }
+
+    /// Returns a point of the sphere visible from the point `p`, uniformly distributed in the solid angle that the
+    /// sphere subtends from `p`, given two random numbers in [0, 1).
+    /// The normal is the outward normal, and the pdf is per unit of solid angle.
+    /// If the point is inside the sphere (or in its surface), it returns Option None.
+    pub fn sample_solid_angle(&self, p: &Vector3, u1: f64, u2: f64) -> Option<SurfaceSample> {
+        let axis: Vector3 = self.c - *p;
+        let dist: f64 = axis.magnitude();
+        if dist <= self.r {
+            return None;
+        }
+
+        // Uniform direction in the cone that contains the sphere
+        let sin2_max: f64 = (self.r / dist).powi(2);
+        let cos_max: f64 = (1.0 - sin2_max).sqrt();
+        let cos_theta: f64 = 1.0 - u1 * (1.0 - cos_max);
+        let sin_theta: f64 = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi: f64 = 2.0 * PI * u2;
+        let w: Vector3 = axis / dist;
+        let (u, v) = orthonormal_basis(&w);
+        let dir: Vector3 = u * (sin_theta * phi.cos()) + v * (sin_theta * phi.sin()) + w * cos_theta;
+
+        // First intersection of the direction with the sphere (tangent at the border of the cone)
+        let t: f64 = dist * cos_theta - (self.r * self.r - (dist * sin_theta).powi(2)).max(0.0).sqrt();
+        let point: Vector3 = *p + dir * t;
+        let normal: Vector3 = (point - self.c).normalize();
+        Some(SurfaceSample::new(&point, &normal, 1.0 / (2.0 * PI * (1.0 - cos_max))))
+    }
+}
+
+impl Triangle3 {
+    /// Returns a point uniformly distributed in the triangle, given two random numbers in [0, 1).
+    /// The normal is the normal of the triangle, and the pdf is per unit of area.
+    pub fn sample_surface(&self, u1: f64, u2: f64) -> SurfaceSample {
+        // Uniform barycentric coordinates (square root warping, so the density doesn´t concentrate in a vertex)
+        let s: f64 = u1.sqrt();
+        let bar_b: f64 = s * (1.0 - u2);
+        let bar_c: f64 = s * u2;
+        let point: Vector3 = self.a * (1.0 - bar_b - bar_c) + self.b * bar_b + self.c * bar_c;
+        SurfaceSample::new(&point, &self.plane.n, 1.0 / self.area())
+    }
+}
+
+impl Circle3 {
+    /// Returns a point uniformly distributed in the disk bounded by the circle, given two random numbers in [0, 1).
+    /// The normal is the normal of the circle, and the pdf is per unit of area.
+    pub fn sample_disk(&self, u1: f64, u2: f64) -> SurfaceSample {
+        let radius: f64 = self.r * u1.sqrt();
+        let theta: f64 = 2.0 * PI * u2;
+        let point: Vector3 = self.c + (self.u * theta.cos() + self.v * theta.sin()) * radius;
+        SurfaceSample::new(&point, &self.n, 1.0 / (PI * self.r * self.r))
+    }
+}
+
+impl Polygon3 {
+    /// Returns a point uniformly distributed in the polygon (like a quad), given two random numbers in [0, 1).
+    /// The normal is the normal of the polygon, and the pdf is per unit of area.
+    /// The triangles of the polygon are calculated in the first call. A polygon too thin to be triangulated (see
+    /// `Polygon3::triangles`) is sampled along its border, using only u1.
+    pub fn sample_surface(&self, u1: f64, u2: f64) -> SurfaceSample {
+        let triangles: &[Triangle3] = self.triangles();
+        if triangles.is_empty() {
+            return SurfaceSample::new(&border_point(&self.vertices, u1), &self.plane.n, 1.0 / self.area());
+        }
+
+        // Choose a triangle with probability proportional to its area, and reuse u1 inside it
+        let cumulative_areas: &[f64] = self.cumulative_areas();
+        let target: f64 = u1 * cumulative_areas[cumulative_areas.len() - 1];
+        let index: usize = cumulative_areas.partition_point(|&sum| sum <= target).min(triangles.len() - 1);
+        let start: f64 = if index == 0 { 0.0 } else { cumulative_areas[index - 1] };
+        let u: f64 = ((target - start) / (cumulative_areas[index] - start)).clamp(0.0, 1.0);
+        let sample: SurfaceSample = triangles[index].sample_surface(u, u2);
+        SurfaceSample::new(&sample.point, &self.plane.n, 1.0 / self.area())
+    }
+}
+
+// Point of the border of a polygon at the fraction u of its perimeter
+fn border_point(vertices: &[Vector3], u: f64) -> Vector3 {
+    let edges: Vec<(Vector3, Vector3)> = (0..vertices.len()).map(|i| (vertices[i], vertices[(i + 1) % vertices.len()])).collect();
+    let mut target: f64 = u * edges.iter().map(|(a, b)| a.distance_to(*b)).sum::<f64>();
+    for (a, b) in edges.iter() {
+        let length: f64 = a.distance_to(*b);
+        if target <= length && length > 0. {
+            return *a + (*b - *a) * (target / length);
+        }
+        target -= length;
+    }
+    vertices[0]
+}
@@ -0,0 +1,170 @@
+use crate::base::{Shape, Intersection, ClosestPoint};
+use crate::line3::Line3;
+use list::List;
+use vector3::Vector3;
+
+/// Shapes that can be members of a `Scene`: any shape that can be intersected, and whose distance to a point is
+/// known (to find the member a point belongs to, when asking for the normal).
+pub trait SceneShape: Shape + ClosestPoint {}
+
+impl<T: Shape + ClosestPoint> SceneShape for T {}
+
+/// Collection of shapes of different types, each of them with a user ID.
+/// `closest_hit` reports the ID of the member that was hit. Scenes can be nested with `add_scene`: the hits of a
+/// nested scene report the ID of the leaf member that was hit (not the ID of the nested scene), so the IDs must be
+/// unique in the whole tree of scenes.
+#[derive(Default)]
+pub struct Scene {
+    members: Vec<(u64, Member)>, // Members of the scene, with their IDs
+}
+
+// Member of a scene: a shape, or a nested scene whose members keep their own IDs
+enum Member {
+    Shape(Box<dyn SceneShape>),
+    Scene(Scene),
+}
+
+impl Member {
+    fn shape(&self) -> &dyn SceneShape {
+        match self {
+            Member::Shape(shape) => shape.as_ref(),
+            Member::Scene(scene) => scene,
+        }
+    }
+}
+
+impl Scene {
+    /// Creates a new empty `Scene`.
+    pub fn new() -> Scene {
+        Self { members: Vec::new() }
+    }
+
+    /// Adds a shape to the scene, with its ID.
+    /// The ID must be unique in the whole tree of scenes.
+    ///
+    /// A `Scene` added this way is a single shape, so its hits report the given ID. To keep the IDs of its members,
+    /// use `add_scene`.
+    pub fn add<S: SceneShape + 'static>(&mut self, id: u64, shape: S) {
+        self.check_unique(id);
+        self.members.push((id, Member::Shape(Box::new(shape))));
+    }
+
+    /// Adds a nested scene, with its ID.
+    /// The hits of the nested scene report the ID of its member that was hit, and the nested scene as a whole can be
+    /// retrieved with `get`. All the IDs (the one of the nested scene and the ones of its members) must be unique in
+    /// the whole tree of scenes.
+    pub fn add_scene(&mut self, id: u64, scene: Scene) {
+        self.check_unique(id);
+        for member_id in scene.ids() {
+            self.check_unique(member_id);
+        }
+        self.members.push((id, Member::Scene(scene)));
+    }
+
+    /// Returns the member of the scene with a given ID, if any.
+    /// The members of the nested scenes are found too.
+    pub fn get(&self, id: u64) -> Option<&dyn SceneShape> {
+        self.members.iter().find_map(|(member_id, member)| {
+            if *member_id == id {
+                return Some(member.shape());
+            }
+            match member {
+                Member::Scene(scene) => scene.get(id),
+                Member::Shape(_) => None,
+            }
+        })
+    }
+
+    /// Returns the number of members of the scene (a nested scene counts as one member).
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns true if the scene has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns the closest positive intersection with the members of the scene, with the ID of the member that was
+    /// hit (for nested scenes, the ID of the leaf member).
+    /// Hits behind the pivot of the line (lambda <= 0, that some shapes like planes return) are ignored.
+    pub fn closest_hit(&self, line: &Line3) -> Option<(Intersection, u64)> {
+        let mut closest: Option<(Intersection, u64)> = None;
+        for (id, member) in self.members.iter() {
+            let hit: Option<(Intersection, u64)> = match member {
+                Member::Shape(shape) => shape.closest_intersection(line).map(|intersection| (intersection, *id)),
+                Member::Scene(scene) => scene.closest_hit(line),
+            };
+            if let Some((intersection, leaf)) = hit
+                && intersection.lambda > 0.
+                && closest.is_none_or(|(c, _)| intersection.lambda < c.lambda) {
+                closest = Some((intersection, leaf));
+            }
+        }
+        closest
+    }
+
+    // IDs of all the members of the scene, and of its nested scenes
+    fn ids(&self) -> Vec<u64> {
+        let mut ids: Vec<u64> = Vec::new();
+        for (id, member) in self.members.iter() {
+            ids.push(*id);
+            if let Member::Scene(scene) = member {
+                ids.extend(scene.ids());
+            }
+        }
+        ids
+    }
+
+    fn check_unique(&self, id: u64) {
+        if self.get(id).is_some() {
+            panic!("The ID {} is already in the scene", id);
+        }
+    }
+
+    // Member whose surface is the closest one to a point
+    fn closest_member(&self, p: &Vector3) -> &dyn SceneShape {
+        if self.members.is_empty() {
+            panic!("The scene is empty");
+        }
+        self.members.iter()
+            .map(|(_, member)| (member.shape().dist_point(p), member.shape()))
+            .min_by(|(d1, _), (d2, _)| d1.total_cmp(d2))
+            .map(|(_, shape)| shape)
+            .unwrap()
+    }
+}
+
+impl ClosestPoint for Scene {
+    /// Returns the point of the members of the scene closest to a given point.
+    /// The scene cannot be empty.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        self.closest_member(p).closest_point(p)
+    }
+}
+
+impl Shape for Scene {
+    /// Returns the normal (normalized) at a given point, that is the normal of the member whose surface is the
+    /// closest one to the point.
+    /// The scene cannot be empty.
+    fn normal(&self, point: &Vector3) -> Vector3 {
+        self.closest_member(point).normal(point)
+    }
+
+    /// Returns the intersections of all the members of the scene with a line (in no particular order).
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        let mut intersections: List<f64> = List::<f64>::new();
+        for (_, member) in self.members.iter() {
+            for lambda in member.shape().intersects(line).iter() {
+                intersections.push(*lambda);
+            }
+        }
+        intersections
+    }
+
+    /// Returns the closest positive intersection with the members of the scene (see `closest_hit` to know which
+    /// member was hit).
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        self.closest_hit(line).map(|(intersection, _)| intersection)
+    }
+}
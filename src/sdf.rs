@@ -0,0 +1,219 @@
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection};
+use crate::capsule::Capsule;
+use crate::csg::{Csg, CsgOp};
+use crate::line3::Line3;
+use crate::obb::Obb;
+use crate::plane::Plane;
+use crate::sphere::Sphere;
+use crate::tetrahedron::Tetrahedron;
+use crate::torus::Torus;
+use crate::transform::Transform;
+use crate::transformed::Transformed;
+use list::List;
+use vector3::Vector3;
+
+// Step of the central differences used to estimate the gradient
+const GRADIENT_STEP: f64 = 1e-6;
+
+// Default limits of the sphere tracing
+const MAX_STEPS: usize = 256;
+const HIT_DISTANCE: f64 = 1e-7;
+const MAX_DISTANCE: f64 = 1e4;
+
+/// Signed distance fields: shapes defined by the signed distance from any point to their surface (positive outside,
+/// negative inside).
+/// Combinations (smooth operations, repetitions, non-uniform scales) may return a bound instead of the exact
+/// distance, but never more than it, which is enough for sphere tracing.
+pub trait Sdf {
+    /// Returns the signed distance (or a lower bound of it) between a point and the surface.
+    fn distance(&self, p: &Vector3) -> f64;
+
+    /// Returns the gradient (normalized) of the distance at a point, estimated with central differences.
+    /// In the surface, it is the outward normal.
+    fn gradient(&self, p: &Vector3) -> Vector3 {
+        let dx: Vector3 = Vector3::new(GRADIENT_STEP, 0.0, 0.0);
+        let dy: Vector3 = Vector3::new(0.0, GRADIENT_STEP, 0.0);
+        let dz: Vector3 = Vector3::new(0.0, 0.0, GRADIENT_STEP);
+        Vector3::new(
+            self.distance(&(*p + dx)) - self.distance(&(*p - dx)),
+            self.distance(&(*p + dy)) - self.distance(&(*p - dy)),
+            self.distance(&(*p + dz)) - self.distance(&(*p - dz)),
+        ).normalize()
+    }
+}
+
+// The shapes that already know their signed distance
+macro_rules! sdf_from_signed_dist_point {
+    ($($shape:ty),* $(,)?) => {
+        $(
+            impl Sdf for $shape {
+                fn distance(&self, p: &Vector3) -> f64 {
+                    self.signed_dist_point(p)
+                }
+            }
+        )*
+    };
+}
+
+sdf_from_signed_dist_point!(Sphere, Aabb, Obb, Capsule, Torus, Plane, Tetrahedron);
+
+impl<S: Sdf> Sdf for Transformed<S> {
+    /// The distance in object space is divided by the largest scale of the inverse transform, so it is exact for
+    /// rigid transforms and uniform scales, and a bound for non-uniform scales.
+    fn distance(&self, p: &Vector3) -> f64 {
+        let inverse: Transform = self.transform.inverse();
+        self.shape.distance(&inverse.transform_point(p)) / inverse.max_scale()
+    }
+}
+
+impl<A: Sdf, B: Sdf> Sdf for Csg<A, B> {
+    fn distance(&self, p: &Vector3) -> f64 {
+        let (da, db) = (self.a.distance(p), self.b.distance(p));
+        match self.op {
+            CsgOp::Union => da.min(db),
+            CsgOp::Intersection => da.max(db),
+            CsgOp::Difference => da.max(-db),
+        }
+    }
+}
+
+// Polynomial smooth minimum: like min, but blended where both values are closer than k
+fn smooth_min(a: f64, b: f64, k: f64) -> f64 {
+    if k == 0. {
+        return a.min(b);
+    }
+    let h: f64 = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+fn smooth_max(a: f64, b: f64, k: f64) -> f64 {
+    -smooth_min(-a, -b, k)
+}
+
+/// Boolean operation of two signed distance fields, with the edges rounded within a distance `k`.
+#[derive(Clone)]
+pub struct SmoothCsg<A, B> {
+    pub op: CsgOp, // Boolean operation
+    pub a: A,      // First operand
+    pub b: B,      // Second operand
+    pub k: f64,    // Size of the blend between both surfaces (0 for sharp edges)
+}
+
+impl<A, B> SmoothCsg<A, B> {
+    /// Creates a new `SmoothCsg` node.
+    pub fn new(op: CsgOp, a: A, b: B, k: f64) -> SmoothCsg<A, B> {
+        if k < 0. {
+            panic!("The size of the blend cannot be negative");
+        }
+        Self { op, a, b, k }
+    }
+
+    /// Creates the smooth union of two fields.
+    pub fn union(a: A, b: B, k: f64) -> SmoothCsg<A, B> {
+        Self::new(CsgOp::Union, a, b, k)
+    }
+
+    /// Creates the smooth intersection of two fields.
+    pub fn intersection(a: A, b: B, k: f64) -> SmoothCsg<A, B> {
+        Self::new(CsgOp::Intersection, a, b, k)
+    }
+
+    /// Creates the smooth difference of two fields (`a` minus `b`).
+    pub fn difference(a: A, b: B, k: f64) -> SmoothCsg<A, B> {
+        Self::new(CsgOp::Difference, a, b, k)
+    }
+}
+
+impl<A: Sdf, B: Sdf> Sdf for SmoothCsg<A, B> {
+    fn distance(&self, p: &Vector3) -> f64 {
+        let (da, db) = (self.a.distance(p), self.b.distance(p));
+        match self.op {
+            CsgOp::Union => smooth_min(da, db, self.k),
+            CsgOp::Intersection => smooth_max(da, db, self.k),
+            CsgOp::Difference => smooth_max(da, -db, self.k),
+        }
+    }
+}
+
+/// Infinite repetition of a signed distance field in a grid, with a cell of size `period` centered in the origin.
+/// A zero component of the period doesn´t repeat the field along that axis.
+/// The distance is exact only if the shape fits inside its cell.
+#[derive(Clone)]
+pub struct Repeat<S> {
+    pub shape: S,        // Repeated field, around the origin
+    pub period: Vector3, // Size of the cells along each axis
+}
+
+impl<S> Repeat<S> {
+    /// Creates a new `Repeat` field.
+    pub fn new(shape: S, period: &Vector3) -> Repeat<S> {
+        if period.x < 0. || period.y < 0. || period.z < 0. {
+            panic!("The period of the repetition cannot be negative");
+        }
+        Self { shape, period: *period }
+    }
+}
+
+impl<S: Sdf> Sdf for Repeat<S> {
+    fn distance(&self, p: &Vector3) -> f64 {
+        let wrap = |x: f64, period: f64| if period == 0. { x } else { x - period * (x / period).round() };
+        let local: Vector3 = Vector3::new(wrap(p.x, self.period.x), wrap(p.y, self.period.y), wrap(p.z, self.period.z));
+        self.shape.distance(&local)
+    }
+}
+
+/// Adapter to intersect lines with any signed distance field, with sphere tracing: the line is followed in steps as
+/// long as the distance to the surface, until it is closer than `hit_distance`.
+/// The normals are estimated with the gradient of the field.
+#[derive(Clone)]
+pub struct SphereTraced<S> {
+    pub sdf: S,            // Field to intersect
+    pub max_steps: usize,  // Maximum number of steps along the line
+    pub hit_distance: f64, // Distance to the surface considered a hit
+    pub max_distance: f64, // Maximum distance along the line
+}
+
+impl<S> SphereTraced<S> {
+    /// Creates a new `SphereTraced` shape, with the default limits (they can be changed later).
+    pub fn new(sdf: S) -> SphereTraced<S> {
+        Self { sdf, max_steps: MAX_STEPS, hit_distance: HIT_DISTANCE, max_distance: MAX_DISTANCE }
+    }
+}
+
+impl<S: Sdf> Shape for SphereTraced<S> {
+    /// Returns the normal (normalized) of the field at a given point, estimated with its gradient.
+    fn normal(&self, point: &Vector3) -> Vector3 {
+        self.sdf.gradient(point)
+    }
+
+    /// Sphere tracing only finds the first surface along the line, so it returns a list with the closest positive
+    /// intersection (if any).
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        let mut intersections: List<f64> = List::<f64>::new();
+        if let Some(intersection) = self.closest_intersection(line) {
+            intersections.push(intersection.lambda);
+        }
+        intersections
+    }
+
+    /// Returns the closest positive intersection of the field with a line.
+    /// If the line starts inside the field, the exit is found. If there is no hit within the limits (`max_steps`
+    /// and `max_distance`), it returns Option None.
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        let length: f64 = line.qa.sqrt();
+        let dir: Vector3 = line.v / length;
+        let mut t: f64 = 0.0;
+        for _ in 0..self.max_steps {
+            let dist: f64 = self.sdf.distance(&(line.a + dir * t)).abs();
+            if dist < self.hit_distance && t > 0. {
+                return Some(Intersection::new(t / length, None));
+            }
+            t += dist.max(self.hit_distance);
+            if t > self.max_distance {
+                break;
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,77 @@
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, ClosestPoint};
+use crate::line3::Line3;
+use crate::plane::Plane;
+use crate::polygon3::Polygon3;
+use crate::sphere::Sphere;
+use crate::tetrahedron::Tetrahedron;
+use crate::triangle3::Triangle3;
+use list::List;
+use vector3::Vector3;
+
+/// Any of the built-in shapes, dispatched with a `match` instead of a trait object.
+/// Collections of different shapes can be stored contiguously (`Vec<ShapeKind>`) without the indirection of
+/// `Box<dyn Shape>`. Every shape can be converted into a `ShapeKind` with `into()`.
+#[derive(Clone)]
+pub enum ShapeKind {
+    Sphere(Sphere),
+    Plane(Plane),
+    Triangle3(Triangle3),
+    Tetrahedron(Tetrahedron),
+    Polygon3(Polygon3),
+    Aabb(Aabb),
+}
+
+// Calls the same expression with the shape inside every variant (also used by the trait impls in other modules)
+macro_rules! dispatch {
+    ($kind:expr, $shape:ident => $call:expr) => {
+        match $kind {
+            $crate::shape_kind::ShapeKind::Sphere($shape) => $call,
+            $crate::shape_kind::ShapeKind::Plane($shape) => $call,
+            $crate::shape_kind::ShapeKind::Triangle3($shape) => $call,
+            $crate::shape_kind::ShapeKind::Tetrahedron($shape) => $call,
+            $crate::shape_kind::ShapeKind::Polygon3($shape) => $call,
+            $crate::shape_kind::ShapeKind::Aabb($shape) => $call,
+        }
+    };
+}
+
+pub(crate) use dispatch;
+
+macro_rules! shape_kind_from {
+    ($($shape:ident),* $(,)?) => {
+        $(
+            impl From<$shape> for ShapeKind {
+                fn from(shape: $shape) -> ShapeKind {
+                    ShapeKind::$shape(shape)
+                }
+            }
+        )*
+    };
+}
+
+shape_kind_from!(Sphere, Plane, Triangle3, Tetrahedron, Polygon3, Aabb);
+
+impl Shape for ShapeKind {
+    fn normal(&self, point: &Vector3) -> Vector3 {
+        dispatch!(self, shape => shape.normal(point))
+    }
+
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        dispatch!(self, shape => shape.intersects(line))
+    }
+
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        dispatch!(self, shape => shape.closest_intersection(line))
+    }
+}
+
+impl ClosestPoint for ShapeKind {
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        dispatch!(self, shape => shape.closest_point(p))
+    }
+
+    fn dist_point(&self, p: &Vector3) -> f64 {
+        dispatch!(self, shape => shape.dist_point(p))
+    }
+}
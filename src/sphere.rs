@@ -1,5 +1,8 @@
-use crate::base::{Shape, Intersection};
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, ClosestPoint, Support, Bounded};
+use crate::circle3::Circle3;
 use crate::line3::Line3;
+use crate::plane::Plane;
 use list::List;
 use vector3::Vector3;
 
@@ -18,7 +21,76 @@ impl Sphere {
         Self { c: *c, r }
     }
 
+    /// Returns the signed distance between a point and the surface of the sphere.
+    /// It is positive outside the sphere, and negative inside.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        p.distance_to(self.c) - self.r
+    }
+
+    /// Calculates the intersection of the sphere with a plane.
+    /// If the plane doesn´t cut the sphere, it returns Option None.
+    /// If the plane is tangent to the sphere, it returns a circle with radius 0.
+    /// Otherwise, it returns the intersection circle, with the same normal as the plane.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<Circle3> {
+        let dist: f64 = plane.signed_dist_point(&self.c);
+        if dist.abs() > self.r {
+            return None;
+        }
+        let center: Vector3 = self.c - plane.n * dist;
+        Some(Circle3::new(&center, &plane.n, (self.r.powi(2) - dist.powi(2)).sqrt()))
+    }
+
+    /// Calculates the intersection of two spheres.
+    /// If the spheres don´t intersect (or they are concentric), it returns Option None.
+    /// If the spheres are tangent, it returns a circle with radius 0.
+    /// Otherwise, it returns the intersection circle, whose normal points from the center of `self` to the
+    /// center of `other`.
+    pub fn intersect_sphere(&self, other: &Sphere) -> Option<Circle3> {
+        let axis: Vector3 = other.c - self.c;
+        let dist: f64 = axis.magnitude();
+        if dist == 0. || dist > self.r + other.r || dist < (self.r - other.r).abs() {
+            return None;
+        }
 
+        // Distance from the center of self to the plane of the circle
+        let h: f64 = (dist.powi(2) + self.r.powi(2) - other.r.powi(2)) / (2. * dist);
+        let n: Vector3 = axis / dist;
+        Some(Circle3::new(&(self.c + n * h), &n, (self.r.powi(2) - h.powi(2)).max(0.0).sqrt()))
+    }
+}
+
+impl ClosestPoint for Sphere {
+    /// Returns the point of the surface of the sphere closest to a given point.
+    /// If the point is the center of the sphere, any point of the surface would do: the one in the X axis is returned.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let cp: Vector3 = *p - self.c;
+        if cp.is_zero() {
+            return self.c + Vector3::new(self.r, 0.0, 0.0);
+        }
+        self.c + cp.normalize() * self.r
+    }
+
+    /// Returns the (unsigned) distance between a point and the surface of the sphere
+    fn dist_point(&self, p: &Vector3) -> f64 {
+        self.signed_dist_point(p).abs()
+    }
+}
+
+impl Support for Sphere {
+    /// Returns the point of the surface of the sphere farthest in a given direction.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        if dir.is_zero() {
+            return self.c + Vector3::new(self.r, 0.0, 0.0);
+        }
+        self.c + dir.normalize() * self.r
+    }
+}
+
+impl Bounded for Sphere {
+    fn bounding_box(&self) -> Aabb {
+        let r: Vector3 = Vector3::new(self.r, self.r, self.r);
+        Aabb::new(&(self.c - r), &(self.c + r))
+    }
 }
 
 impl Shape for Sphere {
@@ -30,7 +102,8 @@ impl Shape for Sphere {
     /// Returns the intersections of the sphere with a line.
     /// If the line doesn´t collide the sphere, it returns an empty list.
     /// If the line is tangent to the sphere, it returns a list with the single lambda value.
-    /// If the line instersects the sphere, it returns a list with the two intersection lambdas.
+    /// If the line instersects the sphere, it returns a list with the two intersection lambdas: the entry first, and
+    /// then the exit (see `Intervals` for the entry and exit with their normals).
     /// The lambda value is used to calculate the point of intersection with the line.calc_point(lambda).
     fn intersects(&self, line: &Line3) -> List<f64> {
         let mut intersections: List<f64> = List::<f64>::new();
@@ -77,10 +150,13 @@ impl Shape for Sphere {
                 return Some(Intersection::new(intersection2, None));
             }
         } else if discrim == 0. {
-            Some((-b) / (2. * line.qa));
+            let intersection: f64 = (-b) / (2. * line.qa);
+            if intersection > 0. {
+                return Some(Intersection::new(intersection, None));
+            }
         }
 
-        return None;
+        None
     }
 }
 
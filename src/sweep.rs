@@ -0,0 +1,170 @@
+use crate::base::ClosestPoint;
+use crate::line3::Line3;
+use crate::plane::Plane;
+use crate::sphere::Sphere;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+// Tolerance for the sine^2 of the angle between the motion and an edge, to consider them parallel
+const PARALLEL_TOLERANCE: f64 = 1e-12;
+
+/// First contact of a moving shape against another one.
+#[derive(Clone, Copy, Debug)]
+pub struct SweepHit {
+    pub time: f64,       // Fraction of the motion (between 0 and 1) when the shapes touch for the first time
+    pub point: Vector3,  // Contact point, in the surface of the other shape
+    pub normal: Vector3, // Contact normal (normalized), from the other shape to the moving one
+}
+
+impl SweepHit {
+    /// Creates a new `SweepHit`.
+    pub fn new(time: f64, point: &Vector3, normal: &Vector3) -> SweepHit {
+        Self { time, point: *point, normal: *normal }
+    }
+}
+
+/// Continuous collision detection: finds the first contact of a shape that moves in a straight line against a
+/// static one, so fast shapes can´t tunnel through thin ones.
+pub trait Sweeps<T> {
+    /// Moves the shape from its position by the displacement `motion.v` (the pivot of the line is ignored, so
+    /// `Line3::new(&position, &destination)` is the usual motion).
+    /// If the shapes don´t touch during the motion, it returns Option None.
+    /// If they already overlap at the start, it returns a hit with time 0.
+    fn sweep(&self, motion: &Line3, other: &T) -> Option<SweepHit>;
+}
+
+// First time (in [0, 1]) when a point moving from `p` with velocity `v` is at distance `r` of the point `c`.
+// The point must start farther than `r`.
+fn sweep_point_sphere(p: &Vector3, v: &Vector3, c: &Vector3, r: f64) -> Option<f64> {
+    let m: Vector3 = *p - *c;
+    let a: f64 = v.dot(v);
+    let b: f64 = m.dot(v);
+    let k: f64 = m.dot(&m) - r * r;
+    if b >= 0. {
+        // Moving away
+        return None;
+    }
+    let discr: f64 = b * b - a * k;
+    if discr < 0. {
+        return None;
+    }
+    let t: f64 = (-b - discr.sqrt()) / a;
+    if t > 1. { None } else { Some(t.max(0.0)) }
+}
+
+// First time (in [0, 1]) when a point moving from `p` with velocity `v` is at distance `r` of the segment from
+// `s0` to `s1`, touching its interior (the ends are handled as points). The point must start farther than `r`.
+fn sweep_point_segment(p: &Vector3, v: &Vector3, s0: &Vector3, s1: &Vector3, r: f64) -> Option<(f64, Vector3)> {
+    let d: Vector3 = *s1 - *s0;
+    let m: Vector3 = *p - *s0;
+    let dd: f64 = d.dot(&d);
+    let nd: f64 = v.dot(&d);
+    let md: f64 = m.dot(&d);
+    let nn: f64 = v.dot(v);
+
+    // Infinite cylinder around the segment: |(m + v t) x d|^2 = r^2 |d|^2
+    let a: f64 = dd * nn - nd * nd;
+    if a <= PARALLEL_TOLERANCE * dd * nn {
+        return None;
+    }
+    let b: f64 = dd * m.dot(v) - nd * md;
+    let k: f64 = dd * (m.dot(&m) - r * r) - md * md;
+    let discr: f64 = b * b - a * k;
+    if b >= 0. || discr < 0. {
+        return None;
+    }
+    let t: f64 = (-b - discr.sqrt()) / a;
+    if !(0. ..=1.).contains(&t) {
+        return None;
+    }
+    // The contact must be between the ends of the segment
+    let s: f64 = (md + t * nd) / dd;
+    if !(0. ..=1.).contains(&s) {
+        return None;
+    }
+    Some((t, *s0 + d * s))
+}
+
+impl Sweeps<Plane> for Sphere {
+    /// The plane is a solid half-space (its normal points out of the solid side), like in the contact generation.
+    fn sweep(&self, motion: &Line3, other: &Plane) -> Option<SweepHit> {
+        let dist: f64 = other.signed_dist_point(&self.c);
+        if dist <= self.r {
+            return Some(SweepHit::new(0.0, &(self.c - other.n * dist), &other.n));
+        }
+        let speed: f64 = motion.v.dot(&other.n);
+        if speed >= 0. {
+            return None;
+        }
+        let t: f64 = (dist - self.r) / -speed;
+        if t > 1. {
+            return None;
+        }
+        Some(SweepHit::new(t, &(self.c + motion.v * t - other.n * self.r), &other.n))
+    }
+}
+
+impl Sweeps<Sphere> for Sphere {
+    fn sweep(&self, motion: &Line3, other: &Sphere) -> Option<SweepHit> {
+        let radius: f64 = self.r + other.r;
+        let offset: Vector3 = self.c - other.c;
+        if offset.magnitude() <= radius {
+            // Concentric spheres: the normal opposes the motion
+            let normal: Vector3 = if offset.is_zero() { motion.v.normalize() * -1.0 } else { offset.normalize() };
+            return Some(SweepHit::new(0.0, &(other.c + normal * other.r), &normal));
+        }
+        let t: f64 = sweep_point_sphere(&self.c, &motion.v, &other.c, radius)?;
+        let normal: Vector3 = (self.c + motion.v * t - other.c).normalize();
+        Some(SweepHit::new(t, &(other.c + normal * other.r), &normal))
+    }
+}
+
+impl Sweeps<Triangle3> for Sphere {
+    /// The triangle is two-sided: the sphere can hit it from both sides.
+    /// The contact can be in the face, in an edge or in a vertex of the triangle.
+    fn sweep(&self, motion: &Line3, other: &Triangle3) -> Option<SweepHit> {
+        let closest: Vector3 = other.closest_point(&self.c);
+        let offset: Vector3 = self.c - closest;
+        if offset.magnitude() <= self.r {
+            let normal: Vector3 = if offset.is_zero() {
+                if motion.v.dot(&other.plane.n) > 0. { other.plane.n * -1.0 } else { other.plane.n }
+            } else {
+                offset.normalize()
+            };
+            return Some(SweepHit::new(0.0, &closest, &normal));
+        }
+
+        // Face: the sphere touches the plane of the triangle with a point inside the triangle.
+        // If the sphere already crosses the plane (beside the triangle), it can only touch an edge or a vertex first.
+        let dist: f64 = other.plane.signed_dist_point(&self.c);
+        let n: Vector3 = if dist >= 0. { other.plane.n } else { other.plane.n * -1.0 };
+        let speed: f64 = motion.v.dot(&n);
+        if speed < 0. && dist.abs() >= self.r {
+            let t: f64 = (dist.abs() - self.r) / -speed;
+            if t <= 1. {
+                let point: Vector3 = self.c + motion.v * t - n * self.r;
+                let bar: Vector3 = other.barycentric(&point);
+                if bar.x >= 0. && bar.y >= 0. && bar.z >= 0. {
+                    return Some(SweepHit::new(t, &point, &n));
+                }
+            }
+        }
+
+        // Otherwise, the first contact is with an edge or a vertex
+        let mut first: Option<(f64, Vector3)> = None;
+        let mut keep = |hit: Option<(f64, Vector3)>| {
+            if let Some((t, point)) = hit && first.is_none_or(|(min, _)| t < min) {
+                first = Some((t, point));
+            }
+        };
+        for (s0, s1) in [(other.a, other.b), (other.b, other.c), (other.c, other.a)] {
+            keep(sweep_point_segment(&self.c, &motion.v, &s0, &s1, self.r));
+        }
+        for vertex in [other.a, other.b, other.c] {
+            keep(sweep_point_sphere(&self.c, &motion.v, &vertex, self.r).map(|t| (t, vertex)));
+        }
+
+        let (t, point) = first?;
+        Some(SweepHit::new(t, &point, &(self.c + motion.v * t - point).normalize()))
+    }
+}
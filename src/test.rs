@@ -1,6 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use crate::{Line3, Plane, Shape, Sphere, Triangle3, Intersection};
+    use crate::{Line3, Approach, Plane, PlaneIntersection, Shape, Sphere, Triangle3, TriangleIntersection, Tetrahedron, Polygon3, Circle3, Aabb, Overlaps, Intersection, ClosestPoint, triangulate,
+        Capsule, ConvexHull, Separation, Penetration, gjk_distance, gjk_overlaps, epa_penetration,
+        Obb, Contact, Contacts, SweepHit, Sweeps,
+        fresnel_schlick, fresnel_dielectric, SurfaceSample,
+        mesh_area, mesh_volume, mesh_centroid, mesh_inertia,
+        Transform, Transformable, Quaternion, Transformed,
+        Bounded, MovingShape, MovingSphere, Keyframe, Keyframed, Scene, ShapeKind,
+        Hits, HitQuery, Interval, Intervals, mesh_intervals,
+        Csg, CsgOp, Torus, Sdf, SmoothCsg, Repeat, SphereTraced};
     use list::List;
     use vector3::Vector3;
 
@@ -22,7 +30,7 @@ mod tests {
         assert_eq!(dist, 0.0);
 
         let dist: f64 = line.dist_point(&Vector3::new(3.0, 7.0, 5.0));
-        assert_eq!(dist, 7.6157731058639087);
+        assert_eq!(dist, 7.615_773_105_863_909);
     }
 
     #[test]
@@ -52,15 +60,19 @@ mod tests {
         // tangent line
         let tangent_instersection: List<f64> = sphere.intersects(&tangent_line);
         assert_eq!(tangent_instersection.iter().count(), 1);
+        // The tangent point is returned when it is in front of the line, and not when it is behind
+        assert_eq!(sphere.closest_intersection(&tangent_line).unwrap().lambda, 1.0);
+        let tangent_behind: Line3 =
+            Line3::new(&Vector3::new(0.0, 2.0, -5.0), &Vector3::new(0.0, 2.0, -10.0));
+        assert!(sphere.closest_intersection(&tangent_behind).is_none());
 
         // non-crossing line
         let outer_instersection: List<f64> = sphere.intersects(&outer_line);
         assert_eq!(outer_instersection.iter().count(), 0);
-        assert_eq!(
+        assert!(
             sphere
                 .closest_intersection(&line_from_beyond_sphere)
-                .is_none(),
-            true
+                .is_none()
         );
     }
 
@@ -83,15 +95,20 @@ mod tests {
 
         let mut instersection: List<f64> = plane.intersects(&horizontal_line);
         assert_eq!(instersection.iter().count(), 0);
-        assert_eq!(plane.closest_intersection(&horizontal_line).is_none(), true);
+        assert!(plane.closest_intersection(&horizontal_line).is_none());
         assert_eq!(plane.closest_intersection(&tilted_line).unwrap().lambda, 0.5);
-        assert_eq!(plane.closest_intersection(&in_plane_line).is_none(), true);
+        assert!(plane.closest_intersection(&in_plane_line).is_none());
 
         instersection = plane.intersects(&tilted_line);
         assert_eq!(instersection.iter().count(), 1);
 
         instersection = plane.intersects(&in_plane_line);
         assert_eq!(instersection.iter().count(), 0);
+
+        // Non normalized normal, with the pivot point out of the origin
+        let shifted_plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 5.0), &Vector3::new(0.0, 0.0, 3.0));
+        let vertical_line: Line3 = Line3::new(&Vector3::new(1.0, 1.0, 0.0), &Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(shifted_plane.closest_intersection(&vertical_line).unwrap().lambda, 5.0);
     }
 
     #[test]
@@ -131,10 +148,1857 @@ mod tests {
         let intersections: List<f64> = triangle.intersects(&ray_outside);
         assert_eq!(intersections.iter().count(), 0);
         intersection = triangle.closest_intersection(&ray_outside);
-        assert_eq!(intersection.is_none(), true);
+        assert!(intersection.is_none());
 
         // Point outside triangle plane, but inside if projected on plane
         barycentric = triangle.barycentric(&Vector3::new(0.0, 5.0, 5.0));
         assert_eq!(barycentric, Vector3::new(0.0, 0.5, 0.5));
     }
+
+    #[test]
+    fn tetrahedron_tests() {
+        let a: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+        let b: Vector3 = Vector3::new(10.0, 0.0, 0.0);
+        let c: Vector3 = Vector3::new(0.0, 10.0, 0.0);
+        let d: Vector3 = Vector3::new(0.0, 0.0, 10.0);
+
+        let result = std::panic::catch_unwind(|| {
+            let _flat = Tetrahedron::new(&a, &b, &c, &Vector3::new(5.0, 5.0, 0.0));
+        });
+        assert!(result.is_err());
+
+        let tetrahedron: Tetrahedron = Tetrahedron::new(&a, &b, &c, &d);
+        assert!((tetrahedron.volume() - 1000.0 / 6.0).abs() < 1e-9);
+
+        // barycentric function: Point matching vertexes
+        assert_eq!(tetrahedron.barycentric(&a), [1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(tetrahedron.barycentric(&b), [0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(tetrahedron.barycentric(&d), [0.0, 0.0, 0.0, 1.0]);
+        let barycentric: [f64; 4] = tetrahedron.barycentric(&Vector3::new(2.0, 3.0, 4.0));
+        for (bar, expected) in barycentric.iter().zip([0.1, 0.2, 0.3, 0.4]) {
+            assert!((bar - expected).abs() < 1e-12);
+        }
+
+        // Vertex order doesn´t change containment
+        let flipped: Tetrahedron = Tetrahedron::new(&b, &a, &c, &d);
+        assert!(tetrahedron.contains(&Vector3::new(1.0, 1.0, 1.0)));
+        assert!(flipped.contains(&Vector3::new(1.0, 1.0, 1.0)));
+        assert!(!tetrahedron.contains(&Vector3::new(5.0, 5.0, 5.0)));
+        assert!(!flipped.contains(&Vector3::new(-1.0, 1.0, 1.0)));
+
+        // Line crossing from face a (x + y + z = 10) to face c (y = 0)
+        let line: Line3 = Line3::new(&Vector3::new(2.0, 10.0, 2.0), &Vector3::new(2.0, 9.0, 2.0));
+        let [entry, exit] = tetrahedron.entry_exit(&line).unwrap();
+        assert!((entry.0 - 4.0).abs() < 1e-9 && entry.1 == 0);
+        assert!((exit.0 - 10.0).abs() < 1e-9 && exit.1 == 2);
+        assert_eq!(tetrahedron.intersects(&line).iter().count(), 2);
+        assert_eq!(tetrahedron.closest_intersection(&line).unwrap().lambda, entry.0);
+        assert_eq!(tetrahedron.normal(&line.calc_point(entry.0)), Vector3::new(1.0, 1.0, 1.0).normalize());
+        assert_eq!(tetrahedron.normal(&line.calc_point(exit.0)), Vector3::new(0.0, -1.0, 0.0));
+
+        // Line starting inside returns the exit
+        let inside: Line3 = Line3::new(&Vector3::new(2.0, 2.0, 2.0), &Vector3::new(2.0, 1.0, 2.0));
+        assert!((tetrahedron.closest_intersection(&inside).unwrap().lambda - 2.0).abs() < 1e-9);
+
+        // Line missing the tetrahedron
+        let outside: Line3 = Line3::new(&Vector3::new(20.0, 10.0, 2.0), &Vector3::new(20.0, 9.0, 2.0));
+        assert!(tetrahedron.entry_exit(&outside).is_none());
+        assert_eq!(tetrahedron.intersects(&outside).iter().count(), 0);
+        assert!(tetrahedron.closest_intersection(&outside).is_none());
+    }
+
+    #[test]
+    fn polygon_tests() {
+        let result = std::panic::catch_unwind(|| {
+            let _non_planar = Polygon3::new(&[
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(10.0, 0.0, 0.0),
+                Vector3::new(10.0, 10.0, 1.0),
+                Vector3::new(0.0, 10.0, 0.0),
+            ]);
+        });
+        assert!(result.is_err());
+
+        // Concave "L" shape, in the plane z = 5
+        let polygon: Polygon3 = Polygon3::new(&[
+            Vector3::new(0.0, 0.0, 5.0),
+            Vector3::new(10.0, 0.0, 5.0),
+            Vector3::new(10.0, 5.0, 5.0),
+            Vector3::new(5.0, 5.0, 5.0),
+            Vector3::new(5.0, 10.0, 5.0),
+            Vector3::new(0.0, 10.0, 5.0),
+        ]);
+        assert_eq!(polygon.plane.n, Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(polygon.area(), 75.0);
+        assert!(polygon.centroid().distance_to(Vector3::new(25.0 / 6.0, 25.0 / 6.0, 5.0)) < 1e-9);
+
+        // Point in polygon, including the notch of the concave side and the borders
+        assert!(polygon.contains(&Vector3::new(2.0, 8.0, 5.0)));
+        assert!(polygon.contains(&Vector3::new(8.0, 2.0, 5.0)));
+        assert!(!polygon.contains(&Vector3::new(8.0, 8.0, 5.0)));
+        assert!(polygon.contains(&Vector3::new(7.0, 5.0, 5.0)));
+        assert!(polygon.contains(&Vector3::new(0.0, 0.0, 5.0)));
+        assert!(!polygon.contains(&Vector3::new(-1.0, 5.0, 5.0)));
+
+        let ray_inside: Line3 = Line3::new(&Vector3::new(2.0, 8.0, 0.0), &Vector3::new(2.0, 8.0, 1.0));
+        assert_eq!(polygon.intersects(&ray_inside).iter().count(), 1);
+        assert_eq!(polygon.closest_intersection(&ray_inside).unwrap().lambda, 5.0);
+        assert_eq!(polygon.normal(&ray_inside.calc_point(5.0)), Vector3::new(0.0, 0.0, 1.0));
+
+        let ray_notch: Line3 = Line3::new(&Vector3::new(8.0, 8.0, 0.0), &Vector3::new(8.0, 8.0, 1.0));
+        assert_eq!(polygon.intersects(&ray_notch).iter().count(), 0);
+        assert!(polygon.closest_intersection(&ray_notch).is_none());
+
+        let ray_behind: Line3 = Line3::new(&Vector3::new(2.0, 8.0, 10.0), &Vector3::new(2.0, 8.0, 11.0));
+        assert_eq!(polygon.intersects(&ray_behind).iter().count(), 1);
+        assert!(polygon.closest_intersection(&ray_behind).is_none());
+
+        // Tilted polygon, in the plane x + z = 10
+        let tilted: Polygon3 = Polygon3::new(&[
+            Vector3::new(10.0, 0.0, 0.0),
+            Vector3::new(10.0, 10.0, 0.0),
+            Vector3::new(0.0, 10.0, 10.0),
+            Vector3::new(0.0, 0.0, 10.0),
+        ]);
+        assert_eq!(tilted.plane.n, Vector3::new(1.0, 0.0, 1.0).normalize());
+        assert!((tilted.area() - 100.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!(tilted.contains(&Vector3::new(5.0, 5.0, 5.0)));
+        assert!(!tilted.contains(&Vector3::new(5.0, 11.0, 5.0)));
+    }
+
+    #[test]
+    fn triangulation_tests() {
+        let area = |triangles: &Vec<Triangle3>| -> f64 {
+            triangles.iter().map(|t| (t.b - t.a).cross(&(t.c - t.a)).magnitude() / 2.0).sum()
+        };
+
+        // Concave polygon (the "L"), with a duplicated vertex and an aligned one, in a tilted plane
+        let l_shape: Polygon3 = Polygon3::new(&[
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(5.0, 0.0, 5.0),
+            Vector3::new(10.0, 0.0, 10.0),
+            Vector3::new(10.0, 5.0, 10.0),
+            Vector3::new(10.0, 5.0, 10.0),
+            Vector3::new(5.0, 5.0, 5.0),
+            Vector3::new(5.0, 10.0, 5.0),
+            Vector3::new(0.0, 10.0, 0.0),
+        ]);
+        let triangles: Vec<Triangle3> = l_shape.triangulate();
+        assert_eq!(triangles.len(), 4);
+        assert!((area(&triangles) - l_shape.area()).abs() < 1e-9);
+        for triangle in triangles.iter() {
+            assert_eq!(triangle.plane.n, l_shape.plane.n);
+        }
+
+        // Square with two square holes (given in both orientations)
+        let outer: Vec<Vector3> = vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 0.0),
+            Vector3::new(10.0, 10.0, 0.0),
+            Vector3::new(0.0, 10.0, 0.0),
+            Vector3::new(0.0, 0.0, 0.0),
+        ];
+        let holes: Vec<Vec<Vector3>> = vec![
+            vec![
+                Vector3::new(2.0, 2.0, 0.0),
+                Vector3::new(4.0, 2.0, 0.0),
+                Vector3::new(4.0, 4.0, 0.0),
+                Vector3::new(2.0, 4.0, 0.0),
+            ],
+            vec![
+                Vector3::new(6.0, 6.0, 0.0),
+                Vector3::new(6.0, 8.0, 0.0),
+                Vector3::new(8.0, 8.0, 0.0),
+                Vector3::new(8.0, 6.0, 0.0),
+            ],
+        ];
+        let triangles: Vec<Triangle3> = triangulate(&outer, &holes);
+        assert_eq!(triangles.len(), 14);
+        assert!((area(&triangles) - 92.0).abs() < 1e-9);
+        for triangle in triangles.iter() {
+            assert_eq!(triangle.plane.n, Vector3::new(0.0, 0.0, 1.0));
+            // No triangle covers the holes
+            let centroid: Vector3 = (triangle.a + triangle.b + triangle.c) / 3.0;
+            assert!(!(centroid.x > 2.0 && centroid.x < 4.0 && centroid.y > 2.0 && centroid.y < 4.0));
+            assert!(!(centroid.x > 6.0 && centroid.x < 8.0 && centroid.y > 6.0 && centroid.y < 8.0));
+        }
+
+        // Clockwise outer loop: the triangles follow its orientation
+        let reversed: Vec<Vector3> = outer.iter().rev().cloned().collect();
+        let triangles: Vec<Triangle3> = triangulate(&reversed, &[]);
+        assert_eq!(triangles.len(), 2);
+        for triangle in triangles.iter() {
+            assert_eq!(triangle.plane.n, Vector3::new(0.0, 0.0, -1.0));
+        }
+
+        // Degenerated outer loop
+        let aligned: Vec<Vector3> = vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(2.0, 2.0, 2.0),
+        ];
+        assert!(triangulate(&aligned, &[]).is_empty());
+    }
+
+    #[test]
+    fn closest_point_tests() {
+        let p: Vector3 = Vector3::new(3.0, 4.0, 12.0);
+
+        // Line
+        let line: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 10.0));
+        assert_eq!(line.closest_point(&p), Vector3::new(0.0, 0.0, 12.0));
+        assert_eq!(line.project(&p), 1.2);
+        assert_eq!(ClosestPoint::dist_point(&line, &p), 5.0);
+
+        // Plane
+        let plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 2.0), &Vector3::new(0.0, 0.0, 2.0));
+        assert_eq!(plane.closest_point(&p), Vector3::new(3.0, 4.0, 2.0));
+        assert_eq!(plane.signed_dist_point(&p), 10.0);
+        assert_eq!(plane.signed_dist_point(&Vector3::new(3.0, 4.0, -1.0)), -3.0);
+        assert_eq!(plane.dist_point(&Vector3::new(3.0, 4.0, -1.0)), 3.0);
+
+        // Sphere
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 12.0), 2.0);
+        assert_eq!(sphere.closest_point(&p), Vector3::new(1.2, 1.6, 12.0));
+        assert_eq!(sphere.signed_dist_point(&p), 3.0);
+        assert_eq!(sphere.signed_dist_point(&Vector3::new(0.0, 1.0, 12.0)), -1.0);
+        assert_eq!(sphere.dist_point(&Vector3::new(0.0, 1.0, 12.0)), 1.0);
+        assert_eq!(sphere.dist_point(&sphere.c), 2.0);
+
+        // Triangle: face, edge and vertex regions
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(10.0, 0.0, 0.0),
+            &Vector3::new(0.0, 10.0, 0.0),
+        );
+        assert_eq!(triangle.closest_barycentric(&Vector3::new(2.0, 3.0, 5.0)), Vector3::new(0.5, 0.2, 0.3));
+        assert_eq!(triangle.closest_point(&Vector3::new(2.0, 3.0, 5.0)), Vector3::new(2.0, 3.0, 0.0));
+        assert_eq!(triangle.signed_dist_point(&Vector3::new(2.0, 3.0, 5.0)), 5.0);
+        assert_eq!(triangle.signed_dist_point(&Vector3::new(2.0, 3.0, -5.0)), -5.0);
+        assert_eq!(triangle.closest_barycentric(&Vector3::new(4.0, -3.0, 0.0)), Vector3::new(0.6, 0.4, 0.0));
+        assert_eq!(triangle.closest_point(&Vector3::new(10.0, 10.0, 0.0)), Vector3::new(5.0, 5.0, 0.0));
+        assert_eq!(triangle.closest_barycentric(&Vector3::new(-1.0, -1.0, 1.0)), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(triangle.closest_barycentric(&Vector3::new(12.0, -1.0, 0.0)), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(triangle.closest_barycentric(&Vector3::new(-1.0, 12.0, 0.0)), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(triangle.dist_point(&Vector3::new(-3.0, 5.0, 4.0)), 5.0);
+
+        // Tetrahedron: outside and inside points
+        let tetrahedron: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(10.0, 0.0, 0.0),
+            &Vector3::new(0.0, 10.0, 0.0),
+            &Vector3::new(0.0, 0.0, 10.0),
+        );
+        assert_eq!(tetrahedron.closest_point(&Vector3::new(2.0, 3.0, -5.0)), Vector3::new(2.0, 3.0, 0.0));
+        assert_eq!(tetrahedron.signed_dist_point(&Vector3::new(2.0, 3.0, -5.0)), 5.0);
+        assert_eq!(tetrahedron.closest_point(&Vector3::new(1.0, 2.0, 3.0)), Vector3::new(0.0, 2.0, 3.0));
+        assert_eq!(tetrahedron.signed_dist_point(&Vector3::new(1.0, 2.0, 3.0)), -1.0);
+
+        // Polygon: projection inside the concave polygon, and closest point in the border
+        let polygon: Polygon3 = Polygon3::new(&[
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 0.0),
+            Vector3::new(10.0, 5.0, 0.0),
+            Vector3::new(5.0, 5.0, 0.0),
+            Vector3::new(5.0, 10.0, 0.0),
+            Vector3::new(0.0, 10.0, 0.0),
+        ]);
+        assert_eq!(polygon.closest_point(&Vector3::new(2.0, 8.0, -3.0)), Vector3::new(2.0, 8.0, 0.0));
+        assert_eq!(polygon.signed_dist_point(&Vector3::new(2.0, 8.0, -3.0)), -3.0);
+        assert_eq!(polygon.closest_point(&Vector3::new(9.0, 7.0, 0.0)), Vector3::new(9.0, 5.0, 0.0));
+        assert_eq!(polygon.closest_point(&Vector3::new(8.0, 9.0, 4.0)), Vector3::new(5.0, 9.0, 0.0));
+        assert_eq!(polygon.signed_dist_point(&Vector3::new(8.0, 9.0, 4.0)), 5.0);
+    }
+
+    #[test]
+    fn line_approach_tests() {
+        // Skew lines: x axis, and a line parallel to y at z = 5
+        let line1: Line3 = Line3::new(&Vector3::new(-2.0, 0.0, 0.0), &Vector3::new(2.0, 0.0, 0.0));
+        let line2: Line3 = Line3::new(&Vector3::new(1.0, -4.0, 5.0), &Vector3::new(1.0, 4.0, 5.0));
+        let approach: Approach = line1.closest_approach(&line2);
+        assert!(!approach.parallel);
+        assert_eq!(approach.lambda1, 0.75);
+        assert_eq!(approach.lambda2, 0.5);
+        assert_eq!(approach.p1, Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(approach.p2, Vector3::new(1.0, 0.0, 5.0));
+        assert_eq!(approach.dist, 5.0);
+
+        // Segments: the second one ends before reaching the first one
+        let segment2: Line3 = Line3::new(&Vector3::new(1.0, 2.0, 5.0), &Vector3::new(1.0, 4.0, 5.0));
+        let approach: Approach = line1.closest_approach_segment(&segment2);
+        assert_eq!(approach.lambda2, 0.0);
+        assert_eq!(approach.p1, Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(approach.p2, Vector3::new(1.0, 2.0, 5.0));
+        let approach: Approach = line1.closest_approach(&segment2);
+        assert_eq!(approach.lambda2, -1.0);
+        assert_eq!(approach.dist, 5.0);
+
+        // Segments: both clamped to their ends
+        let segment1: Line3 = Line3::new(&Vector3::new(-2.0, 0.0, 0.0), &Vector3::new(-1.0, 0.0, 0.0));
+        let approach: Approach = segment1.closest_approach_segment(&segment2);
+        assert_eq!(approach.p1, Vector3::new(-1.0, 0.0, 0.0));
+        assert_eq!(approach.p2, Vector3::new(1.0, 2.0, 5.0));
+        assert_eq!(approach.dist, 33.0_f64.sqrt());
+
+        // Rays: the second ray goes away from the first one
+        let ray2: Line3 = Line3::new(&Vector3::new(1.0, 2.0, 5.0), &Vector3::new(1.0, 3.0, 5.0));
+        let approach: Approach = line1.closest_approach_ray(&ray2);
+        assert_eq!(approach.lambda2, 0.0);
+        assert_eq!(approach.p1, Vector3::new(1.0, 0.0, 0.0));
+
+        // Intersecting lines
+        let crossing: Line3 = Line3::new(&Vector3::new(0.0, -1.0, 0.0), &Vector3::new(0.0, 1.0, 0.0));
+        let approach: Approach = line1.closest_approach(&crossing);
+        assert_eq!(approach.dist, 0.0);
+        assert_eq!(approach.p1, Vector3::new(0.0, 0.0, 0.0));
+
+        // Parallel lines
+        let parallel: Line3 = Line3::new(&Vector3::new(5.0, 3.0, 4.0), &Vector3::new(7.0, 3.0, 4.0));
+        let approach: Approach = line1.closest_approach(&parallel);
+        assert!(approach.parallel);
+        assert_eq!(approach.p1, line1.a);
+        assert_eq!(approach.p2, Vector3::new(-2.0, 3.0, 4.0));
+        assert_eq!(approach.dist, 5.0);
+
+        // Parallel segments not overlapping
+        let approach: Approach = segment1.closest_approach_segment(&parallel);
+        assert!(approach.parallel);
+        assert_eq!(approach.p1, Vector3::new(-1.0, 0.0, 0.0));
+        assert_eq!(approach.p2, Vector3::new(5.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn plane_intersection_tests() {
+        // Floor, and two walls
+        let floor: Plane = Plane::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 1.0));
+        let wall_x: Plane = Plane::new(&Vector3::new(3.0, 0.0, 0.0), &Vector3::new(-2.0, 0.0, 0.0));
+        let wall_y: Plane = Plane::new(&Vector3::new(0.0, 4.0, 7.0), &Vector3::new(0.0, 1.0, 0.0));
+
+        match floor.intersect_plane(&wall_x) {
+            PlaneIntersection::Line(edge) => {
+                assert_eq!(edge.a, Vector3::new(3.0, 0.0, 0.0));
+                assert_eq!(edge.v, Vector3::new(0.0, -1.0, 0.0));
+                assert_eq!(floor.dist_point(&edge.calc_point(10.0)), 0.0);
+                assert_eq!(wall_x.dist_point(&edge.calc_point(10.0)), 0.0);
+            }
+            _ => panic!("The planes should intersect in a line"),
+        }
+
+        // Tilted planes
+        let tilted: Plane = Plane::new(&Vector3::new(0.0, 0.0, 5.0), &Vector3::new(1.0, 1.0, 1.0));
+        match tilted.intersect_plane(&wall_y) {
+            PlaneIntersection::Line(edge) => {
+                for lambda in [-3.0, 0.0, 8.0] {
+                    assert!(tilted.dist_point(&edge.calc_point(lambda)) < 1e-12);
+                    assert!(wall_y.dist_point(&edge.calc_point(lambda)) < 1e-12);
+                }
+            }
+            _ => panic!("The planes should intersect in a line"),
+        }
+
+        // Parallel and coincident planes (also with opposite normals, and nearly parallel)
+        let ceiling: Plane = Plane::new(&Vector3::new(1.0, 2.0, 3.0), &Vector3::new(0.0, 0.0, -1.0));
+        assert!(matches!(floor.intersect_plane(&ceiling), PlaneIntersection::Parallel));
+        let floor_down: Plane = Plane::new(&Vector3::new(5.0, 5.0, 0.0), &Vector3::new(0.0, 0.0, -3.0));
+        assert!(matches!(floor.intersect_plane(&floor_down), PlaneIntersection::Coincident));
+        let nearly: Plane = Plane::new(&Vector3::new(0.0, 0.0, 3.0), &Vector3::new(1e-9, 0.0, 1.0));
+        assert!(matches!(floor.intersect_plane(&nearly), PlaneIntersection::Parallel));
+
+        // Corner of the room
+        assert_eq!(floor.intersect_planes(&wall_x, &wall_y), Some(Vector3::new(3.0, 4.0, 0.0)));
+        let corner: Vector3 = tilted.intersect_planes(&wall_x, &wall_y).unwrap();
+        assert!(corner.distance_to(Vector3::new(3.0, 4.0, -2.0)) < 1e-12);
+        assert!(floor.intersect_planes(&ceiling, &wall_y).is_none());
+
+        // Three planes sharing the same line
+        let diagonal: Plane = Plane::new(&Vector3::new(3.0, 0.0, 0.0), &Vector3::new(1.0, 0.0, 1.0));
+        assert!(floor.intersect_planes(&wall_x, &diagonal).is_none());
+    }
+
+    #[test]
+    fn circle_tests() {
+        let result = std::panic::catch_unwind(|| {
+            let _wrong_circle = Circle3::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 0.0), 1.0);
+        });
+        assert!(result.is_err());
+
+        let circle: Circle3 = Circle3::new(&Vector3::new(1.0, 2.0, 3.0), &Vector3::new(0.0, 0.0, 2.0), 5.0);
+        assert_eq!(circle.n, Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(circle.plane().n, circle.n);
+
+        // Points of the circle, anticlockwise around the normal
+        let p0: Vector3 = circle.calc_point(0.0);
+        let p1: Vector3 = circle.calc_point(std::f64::consts::FRAC_PI_2);
+        assert!((p0.distance_to(circle.c) - 5.0).abs() < 1e-12);
+        assert!(((p0 - circle.c).cross(&(p1 - circle.c)).normalize() - circle.n).is_zero());
+        let samples: Vec<Vector3> = circle.sample(8);
+        assert_eq!(samples.len(), 8);
+        assert_eq!(samples[0], p0);
+        assert!(samples.iter().all(|p| (p.distance_to(circle.c) - 5.0).abs() < 1e-12 && p.z == 3.0));
+
+        // Closest points
+        assert_eq!(circle.closest_point(&Vector3::new(11.0, 2.0, 7.0)), Vector3::new(6.0, 2.0, 3.0));
+        assert_eq!(circle.dist_point(&Vector3::new(1.0, 2.0, 3.0)), 5.0);
+        assert_eq!(circle.dist_point(&Vector3::new(1.0, -1.0, 7.0)), 20.0_f64.sqrt());
+
+        // Sphere and plane
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 5.0);
+        let plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 3.0), &Vector3::new(0.0, 0.0, -1.0));
+        let section: Circle3 = sphere.intersect_plane(&plane).unwrap();
+        assert_eq!(section.c, Vector3::new(0.0, 0.0, 3.0));
+        assert_eq!(section.n, Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(section.r, 4.0);
+        let tangent: Plane = Plane::new(&Vector3::new(5.0, 0.0, 0.0), &Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(sphere.intersect_plane(&tangent).unwrap().r, 0.0);
+        let far: Plane = Plane::new(&Vector3::new(0.0, 6.0, 0.0), &Vector3::new(0.0, 1.0, 0.0));
+        assert!(sphere.intersect_plane(&far).is_none());
+
+        // Sphere and sphere
+        let other: Sphere = Sphere::new(&Vector3::new(0.0, 6.0, 0.0), 5.0);
+        let lens: Circle3 = sphere.intersect_sphere(&other).unwrap();
+        assert_eq!(lens.c, Vector3::new(0.0, 3.0, 0.0));
+        assert_eq!(lens.n, Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(lens.r, 4.0);
+        let touching: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 7.0), 2.0);
+        assert_eq!(sphere.intersect_sphere(&touching).unwrap().r, 0.0);
+        assert!(sphere.intersect_sphere(&Sphere::new(&Vector3::new(0.0, 0.0, 8.0), 2.0)).is_none());
+        assert!(sphere.intersect_sphere(&Sphere::new(&Vector3::new(0.0, 0.0, 1.0), 2.0)).is_none());
+        assert!(sphere.intersect_sphere(&Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 5.0)).is_none());
+    }
+
+    #[test]
+    fn triangle_intersection_tests() {
+        let horizontal: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(10.0, 0.0, 0.0),
+            &Vector3::new(0.0, 10.0, 0.0),
+        );
+
+        // Vertical triangle crossing the horizontal one
+        let crossing: Triangle3 = Triangle3::new(
+            &Vector3::new(2.0, 2.0, -5.0),
+            &Vector3::new(2.0, 2.0, 5.0),
+            &Vector3::new(2.0, 20.0, 5.0),
+        );
+        match horizontal.intersect_triangle(&crossing) {
+            TriangleIntersection::Segment(p, q) => {
+                let (low, high) = if p.y < q.y { (p, q) } else { (q, p) };
+                assert!(low.distance_to(Vector3::new(2.0, 2.0, 0.0)) < 1e-12);
+                assert!(high.distance_to(Vector3::new(2.0, 8.0, 0.0)) < 1e-12);
+            }
+            other => panic!("Expected a segment, got {:?}", other),
+        }
+
+        // Vertex of a triangle touching the other one
+        let touching: Triangle3 = Triangle3::new(
+            &Vector3::new(3.0, 3.0, 0.0),
+            &Vector3::new(3.0, 0.0, 5.0),
+            &Vector3::new(0.0, 3.0, 5.0),
+        );
+        match horizontal.intersect_triangle(&touching) {
+            TriangleIntersection::Point(p) => assert_eq!(p, Vector3::new(3.0, 3.0, 0.0)),
+            other => panic!("Expected a point, got {:?}", other),
+        }
+
+        // Triangles in different sides, and a triangle crossing the plane but out of the other triangle
+        let above: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 1.0),
+            &Vector3::new(10.0, 0.0, 1.0),
+            &Vector3::new(0.0, 10.0, 2.0),
+        );
+        assert!(matches!(horizontal.intersect_triangle(&above), TriangleIntersection::None));
+        let apart: Triangle3 = Triangle3::new(
+            &Vector3::new(8.0, 8.0, -5.0),
+            &Vector3::new(8.0, 8.0, 5.0),
+            &Vector3::new(8.0, 20.0, 5.0),
+        );
+        assert!(matches!(horizontal.intersect_triangle(&apart), TriangleIntersection::None));
+        assert!(matches!(apart.intersect_triangle(&horizontal), TriangleIntersection::None));
+
+        // Coplanar triangles overlapping: the square [0, 5] x [0, 5] cut by the diagonal of the horizontal triangle
+        let coplanar: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(5.0, 0.0, 0.0),
+            &Vector3::new(5.0, 5.0, 0.0),
+        );
+        match horizontal.intersect_triangle(&coplanar) {
+            TriangleIntersection::Coplanar(polygon) => assert_eq!(Polygon3::new(&polygon).area(), 12.5),
+            other => panic!("Expected a coplanar polygon, got {:?}", other),
+        }
+        let square_corner: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(8.0, 0.0, 0.0),
+            &Vector3::new(8.0, 8.0, 0.0),
+        );
+        match horizontal.intersect_triangle(&square_corner) {
+            TriangleIntersection::Coplanar(polygon) => {
+                let overlap: Polygon3 = Polygon3::new(&polygon);
+                assert!((overlap.area() - 23.0).abs() < 1e-9);
+                assert_eq!(overlap.plane.n, horizontal.plane.n);
+            }
+            other => panic!("Expected a coplanar polygon, got {:?}", other),
+        }
+
+        // Coplanar triangles sharing an edge, and apart
+        let neighbour: Triangle3 = Triangle3::new(
+            &Vector3::new(10.0, 0.0, 0.0),
+            &Vector3::new(10.0, 10.0, 0.0),
+            &Vector3::new(0.0, 10.0, 0.0),
+        );
+        match horizontal.intersect_triangle(&neighbour) {
+            TriangleIntersection::Segment(p, q) => {
+                assert!((p.distance_to(q) - 200.0_f64.sqrt()).abs() < 1e-9);
+            }
+            other => panic!("Expected a segment, got {:?}", other),
+        }
+        let far: Triangle3 = Triangle3::new(
+            &Vector3::new(20.0, 0.0, 0.0),
+            &Vector3::new(30.0, 0.0, 0.0),
+            &Vector3::new(20.0, 10.0, 0.0),
+        );
+        assert!(matches!(horizontal.intersect_triangle(&far), TriangleIntersection::None));
+    }
+
+    #[test]
+    fn aabb_tests() {
+        let aabb: Aabb = Aabb::new(&Vector3::new(2.0, 4.0, 6.0), &Vector3::new(-2.0, 0.0, 0.0));
+        assert_eq!(aabb.min, Vector3::new(-2.0, 0.0, 0.0));
+        assert_eq!(aabb.max, Vector3::new(2.0, 4.0, 6.0));
+        assert_eq!(aabb.center(), Vector3::new(0.0, 2.0, 3.0));
+        assert_eq!(aabb.half_extents(), Vector3::new(2.0, 2.0, 3.0));
+        let points: Aabb = Aabb::from_points(&aabb.vertices());
+        assert_eq!((points.min, points.max), (aabb.min, aabb.max));
+        assert!(aabb.contains(&Vector3::new(1.0, 1.0, 1.0)));
+        assert!(!aabb.contains(&Vector3::new(1.0, 5.0, 1.0)));
+
+        // Line through the box
+        let line: Line3 = Line3::new(&Vector3::new(-10.0, 1.0, 1.0), &Vector3::new(-9.0, 1.0, 1.0));
+        assert_eq!(aabb.entry_exit(&line), Some((8.0, 12.0)));
+        assert_eq!(aabb.intersects(&line).iter().count(), 2);
+        assert_eq!(aabb.closest_intersection(&line).unwrap().lambda, 8.0);
+        assert_eq!(aabb.normal(&line.calc_point(8.0)), Vector3::new(-1.0, 0.0, 0.0));
+        assert_eq!(aabb.normal(&line.calc_point(12.0)), Vector3::new(1.0, 0.0, 0.0));
+        let inside: Line3 = Line3::new(&Vector3::new(0.0, 1.0, 1.0), &Vector3::new(0.0, 1.0, 2.0));
+        assert_eq!(aabb.closest_intersection(&inside).unwrap().lambda, 5.0);
+        let outside: Line3 = Line3::new(&Vector3::new(-10.0, 5.0, 1.0), &Vector3::new(-9.0, 5.0, 1.0));
+        assert!(aabb.entry_exit(&outside).is_none());
+        assert!(aabb.closest_intersection(&outside).is_none());
+
+        // Closest points
+        assert_eq!(aabb.closest_point(&Vector3::new(5.0, 2.0, -1.0)), Vector3::new(2.0, 2.0, 0.0));
+        assert_eq!(aabb.closest_point(&Vector3::new(1.5, 2.0, 3.0)), Vector3::new(2.0, 2.0, 3.0));
+        assert_eq!(aabb.signed_dist_point(&Vector3::new(1.5, 2.0, 3.0)), -0.5);
+        assert_eq!(aabb.signed_dist_point(&Vector3::new(5.0, 8.0, 3.0)), 5.0);
+    }
+
+    #[test]
+    fn overlap_tests() {
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 0.5), &Vector3::new(0.0, 0.0, 1.0));
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(-2.0, -2.0, 0.9),
+            &Vector3::new(2.0, -2.0, 0.9),
+            &Vector3::new(0.0, 2.0, 0.9),
+        );
+        let aabb: Aabb = Aabb::new(&Vector3::new(0.5, 0.5, 0.5), &Vector3::new(3.0, 3.0, 3.0));
+        let tetrahedron: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(0.0, 0.0, -0.5),
+            &Vector3::new(5.0, 0.0, -0.5),
+            &Vector3::new(0.0, 5.0, -0.5),
+            &Vector3::new(0.0, 0.0, 4.5),
+        );
+        // Concave "L" whose notch is over the sphere, the box and the tetrahedron
+        let polygon: Polygon3 = Polygon3::new(&[
+            Vector3::new(-3.0, -3.0, 1.0),
+            Vector3::new(3.0, -3.0, 1.0),
+            Vector3::new(3.0, -0.5, 1.0),
+            Vector3::new(-0.5, -0.5, 1.0),
+            Vector3::new(-0.5, 3.0, 1.0),
+            Vector3::new(-3.0, 3.0, 1.0),
+        ]);
+
+        // Pairs that overlap (tested in both directions)
+        assert!(sphere.overlaps(&Sphere::new(&Vector3::new(2.0, 0.0, 0.0), 1.0)));
+        assert!(sphere.overlaps(&plane) && plane.overlaps(&sphere));
+        assert!(sphere.overlaps(&triangle) && triangle.overlaps(&sphere));
+        assert!(sphere.overlaps(&aabb) && aabb.overlaps(&sphere));
+        assert!(sphere.overlaps(&tetrahedron) && tetrahedron.overlaps(&sphere));
+        assert!(Sphere::new(&Vector3::new(0.0, -1.0, 0.5), 0.6).overlaps(&polygon));
+        assert!(polygon.overlaps(&Sphere::new(&Vector3::new(0.0, -1.0, 0.5), 0.6)));
+        assert!(plane.overlaps(&Plane::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 0.0, 0.0))));
+        assert!(plane.overlaps(&Plane::new(&Vector3::new(1.0, 1.0, 0.5), &Vector3::new(0.0, 0.0, -1.0))));
+        assert!(plane.overlaps(&aabb) && aabb.overlaps(&plane));
+        assert!(plane.overlaps(&tetrahedron) && tetrahedron.overlaps(&plane));
+        assert!(triangle.overlaps(&aabb) && aabb.overlaps(&triangle));
+        assert!(triangle.overlaps(&tetrahedron) && tetrahedron.overlaps(&triangle));
+        assert!(triangle.overlaps(&Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(0.0, 0.0, 2.0),
+            &Vector3::new(0.0, 1.0, 2.0),
+        )));
+        assert!(aabb.overlaps(&Aabb::new(&Vector3::new(3.0, 3.0, 3.0), &Vector3::new(4.0, 4.0, 4.0))));
+        assert!(aabb.overlaps(&tetrahedron) && tetrahedron.overlaps(&aabb));
+        let corner_box: Aabb = Aabb::new(&Vector3::new(-1.0, -1.0, 0.0), &Vector3::new(0.0, 0.0, 2.0));
+        assert!(corner_box.overlaps(&polygon) && polygon.overlaps(&corner_box));
+        let spike: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(-2.0, -2.0, 0.0),
+            &Vector3::new(2.0, -2.0, 0.0),
+            &Vector3::new(0.0, -1.0, 0.0),
+            &Vector3::new(0.0, -2.0, 3.0),
+        );
+        assert!(spike.overlaps(&polygon) && polygon.overlaps(&spike));
+        assert!(tetrahedron.overlaps(&Tetrahedron::new(
+            &Vector3::new(1.0, 1.0, 0.0),
+            &Vector3::new(9.0, 1.0, 0.0),
+            &Vector3::new(1.0, 9.0, 0.0),
+            &Vector3::new(1.0, 1.0, 9.0),
+        )));
+        assert!(polygon.overlaps(&Polygon3::new(&[
+            Vector3::new(-1.0, -1.0, 0.0),
+            Vector3::new(-1.0, -1.0, 2.0),
+            Vector3::new(-1.0, 1.0, 2.0),
+        ])));
+        // A very thin polygon still has triangles, so a triangle crossing it is detected
+        let strip: Polygon3 = Polygon3::new(&[
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 1e-8, 0.0),
+            Vector3::new(-1.0, 1e-8, 0.0),
+        ]);
+        let crossing: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, -1.0, -1.0),
+            &Vector3::new(0.0, 1.0, -1.0),
+            &Vector3::new(0.0, 0.0, 1.0),
+        );
+        assert!(strip.overlaps(&crossing) && crossing.overlaps(&strip));
+        // Even if it is too thin to be triangulated
+        let sliver: Polygon3 = Polygon3::new(&[
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 1e-12, 0.0),
+            Vector3::new(-1.0, 1e-12, 0.0),
+        ]);
+        assert!(sliver.triangles().is_empty());
+        let beside: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.1, -1.0),
+            &Vector3::new(0.0, 1.0, -1.0),
+            &Vector3::new(0.0, 0.5, 1.0),
+        );
+        assert!(sliver.overlaps(&crossing) && !sliver.overlaps(&beside) && !beside.overlaps(&sliver));
+
+        // Pairs that don´t overlap
+        let far_tetrahedron: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(10.0, 10.0, 10.0),
+            &Vector3::new(15.0, 10.0, 10.0),
+            &Vector3::new(10.0, 15.0, 10.0),
+            &Vector3::new(10.0, 10.0, 15.0),
+        );
+        assert!(!sphere.overlaps(&Sphere::new(&Vector3::new(2.1, 0.0, 0.0), 1.0)));
+        assert!(!sphere.overlaps(&Plane::new(&Vector3::new(0.0, 0.0, 1.5), &Vector3::new(0.0, 0.0, 1.0))));
+        assert!(!sphere.overlaps(&Triangle3::new(
+            &Vector3::new(1.0, 1.0, 0.0),
+            &Vector3::new(3.0, 1.0, 0.0),
+            &Vector3::new(1.0, 3.0, 0.0),
+        )));
+        assert!(!sphere.overlaps(&Aabb::new(&Vector3::new(0.6, 0.6, 0.6), &Vector3::new(3.0, 3.0, 3.0))));
+        assert!(!Sphere::new(&Vector3::new(-2.0, -2.0, -2.0), 1.0).overlaps(&tetrahedron));
+        assert!(!sphere.overlaps(&polygon) && !polygon.overlaps(&sphere));
+        assert!(!plane.overlaps(&Plane::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 1.0))));
+        assert!(!plane.overlaps(&triangle) && !triangle.overlaps(&plane));
+        assert!(!Plane::new(&Vector3::new(0.0, 0.0, 3.5), &Vector3::new(0.0, 0.0, 1.0)).overlaps(&aabb));
+        assert!(!Plane::new(&Vector3::new(0.0, 0.0, 5.0), &Vector3::new(0.0, 0.0, 1.0)).overlaps(&tetrahedron));
+        assert!(!plane.overlaps(&polygon) && !polygon.overlaps(&plane));
+        assert!(!triangle.overlaps(&Triangle3::new(
+            &Vector3::new(0.0, 0.0, 1.0),
+            &Vector3::new(0.0, 0.0, 2.0),
+            &Vector3::new(0.0, 1.0, 2.0),
+        )));
+        // Triangle whose plane cuts the box, but out of it (only an edge cross product separates them)
+        let diagonal: Triangle3 = Triangle3::new(
+            &Vector3::new(3.6, 0.0, 0.0),
+            &Vector3::new(0.0, 3.6, 0.0),
+            &Vector3::new(3.6, 3.6, -10.0),
+        );
+        assert!(!diagonal.overlaps(&Aabb::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 1.0, 1.0))));
+        assert!(!triangle.overlaps(&far_tetrahedron));
+        assert!(!triangle.overlaps(&polygon) && !polygon.overlaps(&triangle));
+        assert!(!aabb.overlaps(&Aabb::new(&Vector3::new(3.1, 0.0, 0.0), &Vector3::new(4.0, 4.0, 4.0))));
+        assert!(!Aabb::new(&Vector3::new(4.0, 4.0, 4.0), &Vector3::new(5.0, 5.0, 5.0)).overlaps(&tetrahedron));
+        assert!(!aabb.overlaps(&polygon) && !polygon.overlaps(&aabb));
+        assert!(!tetrahedron.overlaps(&far_tetrahedron));
+        assert!(!tetrahedron.overlaps(&polygon) && !polygon.overlaps(&tetrahedron));
+        assert!(!polygon.overlaps(&Polygon3::new(&[
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 2.0),
+            Vector3::new(0.0, 2.0, 2.0),
+        ])));
+    }
+
+    #[test]
+    fn gjk_tests() {
+        let close = |a: &Vector3, b: &Vector3, tolerance: f64| a.distance_to(*b) < tolerance;
+
+        // Separated spheres
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let separation: Separation = gjk_distance(&sphere, &Sphere::new(&Vector3::new(3.0, 0.0, 0.0), 1.0)).unwrap();
+        assert!((separation.distance - 1.0).abs() < 1e-6);
+        assert!(close(&separation.point_a, &Vector3::new(1.0, 0.0, 0.0), 1e-3));
+        assert!(close(&separation.point_b, &Vector3::new(2.0, 0.0, 0.0), 1e-3));
+
+        // Overlapping spheres
+        let other: Sphere = Sphere::new(&Vector3::new(1.5, 0.0, 0.0), 1.0);
+        assert!(gjk_distance(&sphere, &other).is_none());
+        assert!(gjk_overlaps(&sphere, &other));
+        let penetration: Penetration = epa_penetration(&sphere, &other).unwrap();
+        assert!((penetration.depth - 0.5).abs() < 1e-4);
+        assert!(close(&penetration.normal, &Vector3::new(1.0, 0.0, 0.0), 1e-2));
+        assert!(epa_penetration(&sphere, &Sphere::new(&Vector3::new(3.0, 0.0, 0.0), 1.0)).is_none());
+
+        // Boxes
+        let aabb: Aabb = Aabb::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 1.0, 1.0));
+        let separation: Separation = gjk_distance(&aabb, &Aabb::new(&Vector3::new(2.0, 0.5, 0.5), &Vector3::new(3.0, 3.0, 3.0))).unwrap();
+        assert!((separation.distance - 1.0).abs() < 1e-9);
+        assert!((separation.point_a.x - 1.0).abs() < 1e-9 && (separation.point_b.x - 2.0).abs() < 1e-9);
+        let separation: Separation = gjk_distance(&aabb, &Aabb::new(&Vector3::new(2.0, 2.0, 2.0), &Vector3::new(3.0, 3.0, 3.0))).unwrap();
+        assert!((separation.distance - 3.0_f64.sqrt()).abs() < 1e-9);
+        assert!(close(&separation.point_a, &Vector3::new(1.0, 1.0, 1.0), 1e-9));
+        assert!(gjk_overlaps(&aabb, &Aabb::new(&Vector3::new(1.0, 0.0, 0.0), &Vector3::new(2.0, 1.0, 1.0))));
+
+        let penetration: Penetration = epa_penetration(&aabb, &Aabb::new(&Vector3::new(0.8, 0.2, 0.2), &Vector3::new(2.0, 0.8, 0.8))).unwrap();
+        assert!((penetration.depth - 0.2).abs() < 1e-9);
+        assert!(close(&penetration.normal, &Vector3::new(1.0, 0.0, 0.0), 1e-9));
+        assert!((penetration.point_a.x - 1.0).abs() < 1e-9 && (penetration.point_b.x - 0.8).abs() < 1e-9);
+
+        // Capsule against sphere and box
+        let capsule: Capsule = Capsule::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 4.0), 0.5);
+        let separation: Separation = gjk_distance(&capsule, &Sphere::new(&Vector3::new(2.0, 0.0, 2.0), 0.5)).unwrap();
+        assert!((separation.distance - 1.0).abs() < 1e-6);
+        let penetration: Penetration = epa_penetration(&capsule, &Aabb::new(&Vector3::new(0.3, -1.0, 1.0), &Vector3::new(2.0, 1.0, 2.0))).unwrap();
+        assert!((penetration.depth - 0.2).abs() < 1e-6);
+        assert!(close(&penetration.normal, &Vector3::new(1.0, 0.0, 0.0), 1e-3));
+
+        // Convex hull against a triangle and a tetrahedron
+        let hull: ConvexHull = ConvexHull::new(&aabb.vertices());
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(-1.0, -1.0, 2.0),
+            &Vector3::new(3.0, -1.0, 2.0),
+            &Vector3::new(-1.0, 3.0, 2.0),
+        );
+        let separation: Separation = gjk_distance(&hull, &triangle).unwrap();
+        assert!((separation.distance - 1.0).abs() < 1e-9);
+        assert!((separation.point_b.z - 2.0).abs() < 1e-9);
+        let tetrahedron: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(0.5, 0.5, 0.9),
+            &Vector3::new(0.0, 0.0, 3.0),
+            &Vector3::new(1.0, 0.0, 3.0),
+            &Vector3::new(0.0, 1.0, 3.0),
+        );
+        let penetration: Penetration = epa_penetration(&hull, &tetrahedron).unwrap();
+        assert!((penetration.depth - 0.1).abs() < 1e-9);
+        assert!(close(&penetration.normal, &Vector3::new(0.0, 0.0, 1.0), 1e-9));
+
+        // Shapes fully inside others
+        assert!(gjk_overlaps(&aabb, &Sphere::new(&Vector3::new(0.5, 0.5, 0.5), 0.1)));
+        let penetration: Penetration = epa_penetration(&Sphere::new(&Vector3::new(0.5, 0.5, 0.5), 0.1), &aabb).unwrap();
+        assert!((penetration.depth - 0.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn contact_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+        let up: Vector3 = Vector3::new(0.0, 0.0, 1.0);
+        let down: Vector3 = Vector3::new(0.0, 0.0, -1.0);
+
+        // Sphere - Sphere
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let contacts: Vec<Contact> = sphere.contacts(&Sphere::new(&Vector3::new(1.5, 0.0, 0.0), 1.0));
+        assert_eq!(contacts.len(), 1);
+        assert!(close(&contacts[0].point, &Vector3::new(0.75, 0.0, 0.0)));
+        assert!(close(&contacts[0].normal, &Vector3::new(1.0, 0.0, 0.0)));
+        assert!((contacts[0].depth - 0.5).abs() < 1e-9);
+        assert!(sphere.contacts(&Sphere::new(&Vector3::new(2.5, 0.0, 0.0), 1.0)).is_empty());
+
+        // Sphere - Plane (the plane is a solid half-space)
+        let plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 0.0), &up);
+        let contacts: Vec<Contact> = Sphere::new(&Vector3::new(0.0, 0.0, 0.5), 1.0).contacts(&plane);
+        assert_eq!(contacts.len(), 1);
+        assert!(close(&contacts[0].point, &Vector3::new(0.0, 0.0, -0.25)));
+        assert!(close(&contacts[0].normal, &down));
+        assert!((contacts[0].depth - 0.5).abs() < 1e-9);
+        let contacts: Vec<Contact> = plane.contacts(&Sphere::new(&Vector3::new(0.0, 0.0, -3.0), 1.0));
+        assert!(close(&contacts[0].normal, &up) && (contacts[0].depth - 4.0).abs() < 1e-9);
+        assert!(plane.contacts(&Sphere::new(&Vector3::new(0.0, 0.0, 1.5), 1.0)).is_empty());
+
+        // Sphere - Triangle3: face and vertex
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(4.0, 0.0, 0.0),
+            &Vector3::new(0.0, 4.0, 0.0),
+        );
+        let contacts: Vec<Contact> = Sphere::new(&Vector3::new(1.0, 1.0, 0.8), 1.0).contacts(&triangle);
+        assert_eq!(contacts.len(), 1);
+        assert!(close(&contacts[0].normal, &down) && (contacts[0].depth - 0.2).abs() < 1e-9);
+        assert!(close(&contacts[0].point, &Vector3::new(1.0, 1.0, -0.1)));
+        let contacts: Vec<Contact> = triangle.contacts(&Sphere::new(&Vector3::new(-0.6, -0.8, 0.0), 1.5));
+        assert!(close(&contacts[0].normal, &Vector3::new(-0.6, -0.8, 0.0)) && (contacts[0].depth - 0.5).abs() < 1e-9);
+        assert!(Sphere::new(&Vector3::new(3.0, 3.0, 0.0), 1.0).contacts(&triangle).is_empty());
+
+        // Box - Plane
+        let resting: Obb = Obb::from(Aabb::new(&Vector3::new(-0.5, -0.5, -0.1), &Vector3::new(0.5, 0.5, 0.9)));
+        let contacts: Vec<Contact> = resting.contacts(&plane);
+        assert_eq!(contacts.len(), 4);
+        assert!(contacts.iter().all(|c| close(&c.normal, &down) && (c.depth - 0.1).abs() < 1e-9 && (c.point.z + 0.05).abs() < 1e-9));
+        let tilted: Obb = Obb::new(&Vector3::new(0.0, 0.0, 0.5), &Vector3::new(1.0, 0.0, 1.0), &Vector3::new(0.0, 1.0, 0.0), &Vector3::new(0.5, 0.5, 0.5));
+        let contacts: Vec<Contact> = plane.contacts(&tilted);
+        assert_eq!(contacts.len(), 2);
+        assert!(contacts.iter().all(|c| close(&c.normal, &up) && (c.depth - (0.5_f64.sqrt() - 0.5)).abs() < 1e-9));
+        let buried: Obb = Obb::new(&Vector3::new(0.0, 0.0, -5.0), &Vector3::new(1.0, 1.0, 0.0), &Vector3::new(0.0, 0.0, 1.0), &Vector3::new(1.0, 1.0, 1.0));
+        let contacts: Vec<Contact> = buried.contacts(&plane);
+        assert_eq!(contacts.len(), 4);
+        assert!(contacts.iter().any(|c| (c.depth - 6.0).abs() < 1e-9));
+        assert!(Obb::from(Aabb::new(&Vector3::new(0.0, 0.0, 0.1), &Vector3::new(1.0, 1.0, 1.0))).contacts(&plane).is_empty());
+        // Box flattened to a segment: its 8 vertexes are 2 aligned points, so there are only 2 contacts
+        let segment: Obb = Obb::new(&Vector3::new(0.0, 0.0, -1.0), &Vector3::new(1.0, 0.0, 1.0), &Vector3::new(0.0, 1.0, 0.0), &Vector3::new(0.5, 0.0, 0.0));
+        let contacts: Vec<Contact> = segment.contacts(&plane);
+        assert_eq!(contacts.len(), 2);
+        assert!(!close(&contacts[0].point, &contacts[1].point));
+
+        // Box - Box: face contact, clipped to the overlapping region
+        let base: Obb = Obb::from(Aabb::new(&Vector3::new(-1.0, -1.0, -1.0), &Vector3::new(1.0, 1.0, 1.0)));
+        let stacked: Obb = Obb::from(Aabb::new(&Vector3::new(-0.5, -0.7, 0.9), &Vector3::new(1.5, 1.3, 2.9)));
+        let contacts: Vec<Contact> = base.contacts(&stacked);
+        assert_eq!(contacts.len(), 4);
+        assert!(contacts.iter().all(|c| close(&c.normal, &up) && (c.depth - 0.1).abs() < 1e-9 && (c.point.z - 0.95).abs() < 1e-9));
+        for corner in [Vector3::new(-0.5, -0.7, 0.95), Vector3::new(1.0, -0.7, 0.95), Vector3::new(-0.5, 1.0, 0.95), Vector3::new(1.0, 1.0, 0.95)] {
+            assert!(contacts.iter().any(|c| close(&c.point, &corner)));
+        }
+        let contacts: Vec<Contact> = stacked.contacts(&base);
+        assert_eq!(contacts.len(), 4);
+        assert!(contacts.iter().all(|c| close(&c.normal, &down)));
+
+        // Rotated box over a face: the clipped octagon is reduced to 4 points
+        let rotated: Obb = Obb::new(&Vector3::new(0.0, 0.0, 1.95), &Vector3::new(1.0, 1.0, 0.0), &Vector3::new(-1.0, 1.0, 0.0), &Vector3::new(1.0, 1.0, 1.0));
+        let contacts: Vec<Contact> = base.contacts(&rotated);
+        assert_eq!(contacts.len(), 4);
+        assert!(contacts.iter().all(|c| close(&c.normal, &up) && (c.depth - 0.05).abs() < 1e-9 && base.to_local(&c.point).x.abs() <= 1.0 + 1e-9));
+
+        // Edge - edge contact between two crossed ridges
+        let ridge_a: Obb = Obb::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 0.0, 1.0), &Vector3::new(0.0, 1.0, 0.0), &Vector3::new(1.0, 1.0, 1.0));
+        let ridge_b: Obb = Obb::new(&Vector3::new(0.0, 0.0, 2.0 * 2.0_f64.sqrt() - 0.1), &Vector3::new(1.0, 0.0, 0.0), &Vector3::new(0.0, 1.0, 1.0), &Vector3::new(1.0, 1.0, 1.0));
+        let contacts: Vec<Contact> = ridge_a.contacts(&ridge_b);
+        assert_eq!(contacts.len(), 1);
+        assert!(close(&contacts[0].normal, &up) && (contacts[0].depth - 0.1).abs() < 1e-9);
+        assert!(close(&contacts[0].point, &Vector3::new(0.0, 0.0, 2.0_f64.sqrt() - 0.05)));
+
+        assert!(base.contacts(&Obb::from(Aabb::new(&Vector3::new(1.1, -1.0, -1.0), &Vector3::new(2.0, 1.0, 1.0)))).is_empty());
+        assert!(ridge_a.contacts(&Obb::new(&Vector3::new(0.0, 0.0, 2.0 * 2.0_f64.sqrt() + 0.1), &Vector3::new(1.0, 0.0, 0.0), &Vector3::new(0.0, 1.0, 1.0), &Vector3::new(1.0, 1.0, 1.0))).is_empty());
+    }
+
+    #[test]
+    fn sweep_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+        let up: Vector3 = Vector3::new(0.0, 0.0, 1.0);
+
+        // Sphere against a plane
+        let plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 0.0), &up);
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 5.0), 1.0);
+        let hit: SweepHit = sphere.sweep(&Line3::new(&sphere.c, &Vector3::new(0.0, 0.0, -5.0)), &plane).unwrap();
+        assert!((hit.time - 0.4).abs() < 1e-9);
+        assert!(close(&hit.point, &Vector3::new(0.0, 0.0, 0.0)) && close(&hit.normal, &up));
+        assert!(sphere.sweep(&Line3::new(&sphere.c, &Vector3::new(0.0, 0.0, 3.0)), &plane).is_none());
+        assert!(sphere.sweep(&Line3::new(&sphere.c, &Vector3::new(0.0, 0.0, 8.0)), &plane).is_none());
+        let resting: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.5), 1.0);
+        assert_eq!(resting.sweep(&Line3::new(&resting.c, &Vector3::new(3.0, 0.0, 0.5)), &plane).unwrap().time, 0.0);
+
+        // Sphere against a sphere
+        let obstacle: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let moving: Sphere = Sphere::new(&Vector3::new(-5.0, 0.0, 0.0), 1.0);
+        let hit: SweepHit = moving.sweep(&Line3::new(&moving.c, &Vector3::new(5.0, 0.0, 0.0)), &obstacle).unwrap();
+        assert!((hit.time - 0.3).abs() < 1e-9);
+        assert!(close(&hit.point, &Vector3::new(-1.0, 0.0, 0.0)) && close(&hit.normal, &Vector3::new(-1.0, 0.0, 0.0)));
+        assert!(moving.sweep(&Line3::new(&moving.c, &Vector3::new(5.0, 3.0, 0.0)), &Sphere::new(&Vector3::new(0.0, 3.0, 0.0), 0.5)).is_some());
+        assert!(moving.sweep(&Line3::new(&moving.c, &Vector3::new(-9.0, 0.0, 0.0)), &obstacle).is_none());
+        let passing: Sphere = Sphere::new(&Vector3::new(-5.0, 3.0, 0.0), 1.0);
+        assert!(passing.sweep(&Line3::new(&passing.c, &Vector3::new(5.0, 3.0, 0.0)), &obstacle).is_none());
+
+        // Sphere against a triangle: face (from both sides), edge and vertex
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(4.0, 0.0, 0.0),
+            &Vector3::new(0.0, 4.0, 0.0),
+        );
+        let above: Sphere = Sphere::new(&Vector3::new(1.0, 1.0, 5.0), 1.0);
+        let hit: SweepHit = above.sweep(&Line3::new(&above.c, &Vector3::new(1.0, 1.0, -5.0)), &triangle).unwrap();
+        assert!((hit.time - 0.4).abs() < 1e-9);
+        assert!(close(&hit.point, &Vector3::new(1.0, 1.0, 0.0)) && close(&hit.normal, &up));
+        let below: Sphere = Sphere::new(&Vector3::new(1.0, 1.0, -5.0), 1.0);
+        let hit: SweepHit = below.sweep(&Line3::new(&below.c, &Vector3::new(1.0, 1.0, 5.0)), &triangle).unwrap();
+        assert!((hit.time - 0.4).abs() < 1e-9 && close(&hit.normal, &Vector3::new(0.0, 0.0, -1.0)));
+
+        let side: Sphere = Sphere::new(&Vector3::new(2.0, -5.0, 0.0), 1.0);
+        let hit: SweepHit = side.sweep(&Line3::new(&side.c, &Vector3::new(2.0, 5.0, 0.0)), &triangle).unwrap();
+        assert!((hit.time - 0.4).abs() < 1e-9);
+        assert!(close(&hit.point, &Vector3::new(2.0, 0.0, 0.0)) && close(&hit.normal, &Vector3::new(0.0, -1.0, 0.0)));
+
+        let corner: Sphere = Sphere::new(&Vector3::new(-3.0, -3.0, 0.0), 1.0);
+        let hit: SweepHit = corner.sweep(&Line3::new(&corner.c, &Vector3::new(3.0, 3.0, 0.0)), &triangle).unwrap();
+        let sqrt2: f64 = 2.0_f64.sqrt();
+        assert!((hit.time - (3.0 * sqrt2 - 1.0) / (6.0 * sqrt2)).abs() < 1e-9);
+        assert!(close(&hit.point, &triangle.a) && close(&hit.normal, &Vector3::new(-1.0 / sqrt2, -1.0 / sqrt2, 0.0)));
+
+        // Starting beside the triangle, already crossing its plane: no hit in the past, the contact is with an edge
+        let beside: Sphere = Sphere::new(&Vector3::new(5.0, 1.0, 0.5), 1.0);
+        assert!(beside.sweep(&Line3::new(&beside.c, &Vector3::new(15.0, 1.0, -0.5)), &triangle).is_none());
+        let beside: Sphere = Sphere::new(&Vector3::new(2.0, -5.0, 0.5), 1.0);
+        let motion: Line3 = Line3::new(&beside.c, &Vector3::new(2.0, 5.0, -0.5));
+        let hit: SweepHit = beside.sweep(&motion, &triangle).unwrap();
+        assert!(hit.time > 0. && hit.time < 0.5);
+        assert!(hit.point.y.abs() < 1e-9 && hit.point.z.abs() < 1e-9);
+        assert!(((beside.c + motion.v * hit.time).distance_to(hit.point) - 1.0).abs() < 1e-9);
+
+        // A fast sphere doesn´t tunnel through the triangle, and a parallel motion misses it
+        let fast: Sphere = Sphere::new(&Vector3::new(1.0, 1.0, 100.0), 0.1);
+        assert!(fast.sweep(&Line3::new(&fast.c, &Vector3::new(1.0, 1.0, -100.0)), &triangle).is_some());
+        let parallel: Sphere = Sphere::new(&Vector3::new(-5.0, 1.0, 2.0), 1.0);
+        assert!(parallel.sweep(&Line3::new(&parallel.c, &Vector3::new(5.0, 1.0, 2.0)), &triangle).is_none());
+    }
+
+    #[test]
+    fn optics_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+
+        // Reflection on a plane and on a sphere
+        let plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 1.0));
+        let line: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 1.0), &Vector3::new(1.0, 0.0, 0.0));
+        let reflected: Line3 = line.reflect(&plane, &plane.closest_intersection(&line).unwrap());
+        assert!(close(&reflected.a, &Vector3::new(1.0, 0.0, 0.0)) && close(&reflected.v, &Vector3::new(1.0, 0.0, 1.0)));
+
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let line: Line3 = Line3::new(&Vector3::new(-5.0, 0.0, 0.0), &Vector3::new(-3.0, 0.0, 0.0));
+        let reflected: Line3 = line.reflect(&sphere, &sphere.closest_intersection(&line).unwrap());
+        assert!(close(&reflected.a, &Vector3::new(-1.0, 0.0, 0.0)) && close(&reflected.v, &Vector3::new(-2.0, 0.0, 0.0)));
+
+        // Refraction: normal incidence doesn´t bend, oblique incidence follows Snell´s law (in both directions)
+        let down: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 1.0), &Vector3::new(0.0, 0.0, 0.0));
+        let refracted: Line3 = down.refract(&plane, &plane.closest_intersection(&down).unwrap(), 1.0 / 1.5).unwrap();
+        assert!(close(&refracted.v, &down.v));
+
+        let oblique: Line3 = Line3::new(&Vector3::new(-1.0, 0.0, 1.0), &Vector3::new(0.0, 0.0, 0.0));
+        let refracted: Line3 = oblique.refract(&plane, &plane.closest_intersection(&oblique).unwrap(), 1.0 / 1.5).unwrap();
+        let sin_t: f64 = refracted.v.x / refracted.v.magnitude();
+        assert!((sin_t - 0.5_f64.sqrt() / 1.5).abs() < 1e-9);
+        assert!(refracted.v.z < 0. && (refracted.v.magnitude() - oblique.v.magnitude()).abs() < 1e-9);
+        let back: Line3 = Line3::new(&refracted.a, &(refracted.a - refracted.v)).refract_at(&refracted.a, &plane.n, 1.5).unwrap();
+        assert!(close(&back.v, &(oblique.v * -1.0)));
+
+        // Total internal reflection (from glass to air at 60 degrees)
+        let inside: Line3 = Line3::new(&Vector3::new(-(3.0_f64.sqrt()), 0.0, -1.0), &Vector3::new(0.0, 0.0, 0.0));
+        assert!(inside.refract(&plane, &plane.closest_intersection(&inside).unwrap(), 1.5).is_none());
+
+        // Fresnel coefficients
+        assert!((fresnel_schlick(1.0, 1.0, 1.5) - 0.04).abs() < 1e-9);
+        assert!((fresnel_dielectric(1.0, 1.0, 1.5) - 0.04).abs() < 1e-9);
+        assert!((fresnel_dielectric(0.0, 1.0, 1.5) - 1.0).abs() < 1e-9);
+        assert!((fresnel_schlick(0.0, 1.0, 1.5) - 1.0).abs() < 1e-9);
+        assert_eq!(fresnel_schlick(0.5, 1.5, 1.0), 1.0);
+        assert_eq!(fresnel_dielectric(0.5, 1.5, 1.0), 1.0);
+        // At Brewster´s angle, the p polarization isn´t reflected
+        let cos_b: f64 = 1.5_f64.atan().cos();
+        let sin_b: f64 = 1.5_f64.atan().sin();
+        let cos_t: f64 = (1.0 - (sin_b / 1.5).powi(2)).sqrt();
+        let rs: f64 = (cos_b - 1.5 * cos_t) / (cos_b + 1.5 * cos_t);
+        assert!((fresnel_dielectric(cos_b, 1.0, 1.5) - rs * rs / 2.0).abs() < 1e-9);
+        assert!((fresnel_schlick(cos_b, 1.0, 1.5) - fresnel_dielectric(cos_b, 1.0, 1.5)).abs() < 0.02);
+    }
+
+    #[test]
+    fn sampling_tests() {
+        use std::f64::consts::PI;
+        // Stratified grid of random numbers, so the tests are deterministic
+        let grid: Vec<(f64, f64)> = (0..20).flat_map(|i| (0..20).map(move |j| ((i as f64 + 0.5) / 20.0, (j as f64 + 0.5) / 20.0))).collect();
+        let mean = |samples: &[SurfaceSample]| samples.iter().fold(Vector3::new(0.0, 0.0, 0.0), |sum, s| sum + s.point) / samples.len() as f64;
+
+        // Sphere
+        let sphere: Sphere = Sphere::new(&Vector3::new(1.0, 2.0, 3.0), 2.0);
+        let samples: Vec<SurfaceSample> = grid.iter().map(|&(u1, u2)| sphere.sample_surface(u1, u2)).collect();
+        for sample in samples.iter() {
+            assert!((sample.point.distance_to(sphere.c) - 2.0).abs() < 1e-9);
+            assert!(sample.normal.distance_to((sample.point - sphere.c) / 2.0) < 1e-9);
+            assert!((sample.pdf - 1.0 / (16.0 * PI)).abs() < 1e-12);
+        }
+        assert!(mean(&samples).distance_to(sphere.c) < 1e-2);
+        assert!(sphere.sample_surface(0.0, 0.3).point.distance_to(Vector3::new(1.0, 2.0, 5.0)) < 1e-9);
+
+        // Triangle
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(3.0, 0.0, 0.0),
+            &Vector3::new(0.0, 3.0, 3.0),
+        );
+        let samples: Vec<SurfaceSample> = grid.iter().map(|&(u1, u2)| triangle.sample_surface(u1, u2)).collect();
+        for sample in samples.iter() {
+            let bar: Vector3 = triangle.barycentric(&sample.point);
+            assert!(bar.x >= -1e-12 && bar.y >= -1e-12 && bar.z >= -1e-12);
+            assert!(triangle.plane.dist_point(&sample.point) < 1e-9 && sample.normal == triangle.plane.n);
+            assert!((sample.pdf - 2.0 / (9.0 * 2.0_f64.sqrt())).abs() < 1e-12);
+        }
+        assert!(mean(&samples).distance_to(Vector3::new(1.0, 1.0, 1.0)) < 2e-2);
+
+        // Disk
+        let circle: Circle3 = Circle3::new(&Vector3::new(0.0, 0.0, 1.0), &Vector3::new(1.0, 1.0, 0.0), 2.0);
+        let samples: Vec<SurfaceSample> = grid.iter().map(|&(u1, u2)| circle.sample_disk(u1, u2)).collect();
+        for sample in samples.iter() {
+            assert!(sample.point.distance_to(circle.c) <= 2.0 + 1e-9 && circle.plane().dist_point(&sample.point) < 1e-9);
+            assert!((sample.pdf - 1.0 / (4.0 * PI)).abs() < 1e-12 && sample.normal == circle.n);
+        }
+        assert!(mean(&samples).distance_to(circle.c) < 1e-2);
+        // The fraction of the samples inside half of the radius is the fraction of the area
+        let inner: usize = samples.iter().filter(|s| s.point.distance_to(circle.c) < 1.0).count();
+        assert_eq!(inner, samples.len() / 4);
+
+        // Quad
+        let quad: Polygon3 = Polygon3::new(&[
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::new(2.0, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        ]);
+        let samples: Vec<SurfaceSample> = grid.iter().map(|&(u1, u2)| quad.sample_surface(u1, u2)).collect();
+        assert!(samples.iter().all(|s| quad.contains(&s.point) && (s.pdf - 0.5).abs() < 1e-12));
+        assert!(mean(&samples).distance_to(Vector3::new(1.0, 0.5, 0.0)) < 2e-2);
+        // The triangles are calculated once, and the extremes of u1 select the first and the last of them
+        assert_eq!(quad.triangles().len(), 2);
+        assert!(quad.triangles()[0].dist_point(&quad.sample_surface(0.0, 0.5).point) < 1e-12);
+        assert!(quad.triangles()[1].dist_point(&quad.sample_surface(0.999_999, 0.5).point) < 1e-12);
+        assert!(quad.contains(&quad.sample_surface(1.0, 1.0).point));
+        // A sliver too thin to be triangulated is sampled along its border
+        let sliver: Polygon3 = Polygon3::new(&[
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 1e-12, 0.0),
+            Vector3::new(0.0, 1e-12, 0.0),
+        ]);
+        assert!(sliver.triangles().is_empty());
+        for &(u1, u2) in grid.iter() {
+            let sample: SurfaceSample = sliver.sample_surface(u1, u2);
+            assert!(sliver.contains(&sample.point) && (sample.pdf - 1e12).abs() < 1e3);
+        }
+
+        // The disk of a circle out of the origin, with its normal out of the axes
+        let tilted: Circle3 = Circle3::new(&Vector3::new(1.0, 2.0, 3.0), &Vector3::new(1.0, 1.0, 1.0), 2.0);
+        for &(u1, u2) in grid.iter() {
+            let sample: SurfaceSample = tilted.sample_disk(u1, u2);
+            assert!(tilted.plane().dist_point(&sample.point) < 1e-9 && sample.point.distance_to(tilted.c) <= 2.0 + 1e-9);
+        }
+
+        // Solid angle of a sphere: visible points, and the integral of 1 / pdf is the solid angle
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let p: Vector3 = Vector3::new(0.0, 0.0, -5.0);
+        let cos_max: f64 = (1.0 - 1.0 / 25.0_f64).sqrt();
+        for &(u1, u2) in grid.iter() {
+            let sample: SurfaceSample = sphere.sample_solid_angle(&p, u1, u2).unwrap();
+            assert!((sample.point.magnitude() - 1.0).abs() < 1e-9);
+            assert!(sample.normal.dot(&(p - sample.point)) >= -1e-9);
+            assert!((sample.pdf - 1.0 / (2.0 * PI * (1.0 - cos_max))).abs() < 1e-9);
+        }
+        assert!(sphere.sample_solid_angle(&p, 0.0, 0.0).unwrap().point.distance_to(Vector3::new(0.0, 0.0, -1.0)) < 1e-9);
+        assert!(sphere.sample_solid_angle(&Vector3::new(0.0, 0.5, 0.0), 0.5, 0.5).is_none());
+    }
+
+    #[test]
+    fn mass_tests() {
+        use std::f64::consts::PI;
+        let same = |m1: &[[f64; 3]; 3], m2: &[[f64; 3]; 3]| (0..3).all(|i| (0..3).all(|j| (m1[i][j] - m2[i][j]).abs() < 1e-9));
+
+        let sphere: Sphere = Sphere::new(&Vector3::new(1.0, 2.0, 3.0), 2.0);
+        assert!((sphere.area() - 16.0 * PI).abs() < 1e-9);
+        assert!((sphere.volume() - 32.0 / 3.0 * PI).abs() < 1e-9);
+        assert!(same(&sphere.inertia(5.0), &[[8.0, 0.0, 0.0], [0.0, 8.0, 0.0], [0.0, 0.0, 8.0]]));
+
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(3.0, 0.0, 0.0),
+            &Vector3::new(0.0, 4.0, 0.0),
+        );
+        assert!((triangle.area() - 6.0).abs() < 1e-12);
+        assert_eq!(triangle.centroid(), Vector3::new(1.0, 4.0 / 3.0, 0.0));
+
+        let capsule: Capsule = Capsule::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 2.0), 1.0);
+        assert!((capsule.area() - 8.0 * PI).abs() < 1e-9);
+        assert!((capsule.volume() - 10.0 / 3.0 * PI).abs() < 1e-9);
+        // Mass split 6 (cylinder) / 4 (hemispheres)
+        assert!(same(&capsule.inertia(10.0), &[[12.1, 0.0, 0.0], [0.0, 12.1, 0.0], [0.0, 0.0, 4.6]]));
+        let lying: Capsule = Capsule::new(&Vector3::new(-1.0, 5.0, 0.0), &Vector3::new(1.0, 5.0, 0.0), 1.0);
+        assert!(same(&lying.inertia(10.0), &[[4.6, 0.0, 0.0], [0.0, 12.1, 0.0], [0.0, 0.0, 12.1]]));
+        let point: Capsule = Capsule::new(&Vector3::new(1.0, 2.0, 3.0), &Vector3::new(1.0, 2.0, 3.0), 2.0);
+        assert!(same(&point.inertia(5.0), &Sphere::new(&point.a, 2.0).inertia(5.0)));
+
+        // Boxes
+        let aabb: Aabb = Aabb::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(2.0, 3.0, 4.0));
+        assert!((aabb.area() - 52.0).abs() < 1e-12 && (aabb.volume() - 24.0).abs() < 1e-12);
+        let box_inertia: [[f64; 3]; 3] = [[25.0, 0.0, 0.0], [0.0, 20.0, 0.0], [0.0, 0.0, 13.0]];
+        assert!(same(&aabb.inertia(12.0), &box_inertia));
+        let obb: Obb = Obb::from(aabb);
+        assert!((obb.area() - 52.0).abs() < 1e-12 && (obb.volume() - 24.0).abs() < 1e-12);
+        assert!(same(&obb.inertia(12.0), &box_inertia));
+        // Rotated 90 degrees around z: the x and y moments swap
+        let rotated: Obb = Obb::new(&obb.c, &Vector3::new(0.0, 1.0, 0.0), &Vector3::new(-1.0, 0.0, 0.0), &obb.half_extents);
+        assert!(same(&rotated.inertia(12.0), &[[20.0, 0.0, 0.0], [0.0, 25.0, 0.0], [0.0, 0.0, 13.0]]));
+        // Rotated 45 degrees around z: products of inertia appear
+        let diagonal: Obb = Obb::new(&obb.c, &Vector3::new(1.0, 1.0, 0.0), &Vector3::new(-1.0, 1.0, 0.0), &obb.half_extents);
+        assert!(same(&diagonal.inertia(12.0), &[[22.5, 2.5, 0.0], [2.5, 22.5, 0.0], [0.0, 0.0, 13.0]]));
+
+        // Closed mesh of the same box, with its faces in anticlockwise order looked from outside
+        let v: [Vector3; 8] = aabb.vertices();
+        let quads: [[usize; 4]; 6] = [[0, 4, 6, 2], [1, 3, 7, 5], [0, 1, 5, 4], [2, 6, 7, 3], [0, 2, 3, 1], [4, 5, 7, 6]];
+        let mesh: Vec<Triangle3> = quads.iter().flat_map(|&[p, q, r, s]| [
+            Triangle3::new(&v[p], &v[q], &v[r]),
+            Triangle3::new(&v[p], &v[r], &v[s]),
+        ]).collect();
+        assert!((mesh_area(&mesh) - 52.0).abs() < 1e-9);
+        assert!((mesh_volume(&mesh) - 24.0).abs() < 1e-9);
+        assert!(mesh_centroid(&mesh).distance_to(aabb.center()) < 1e-9);
+        assert!(same(&mesh_inertia(&mesh, 12.0), &box_inertia));
+        // Clockwise winding gives a negative volume, but the same centroid and inertia
+        let reversed: Vec<Triangle3> = mesh.iter().map(|t| Triangle3::new(&t.a, &t.c, &t.b)).collect();
+        assert!((mesh_volume(&reversed) + 24.0).abs() < 1e-9);
+        assert!(same(&mesh_inertia(&reversed, 12.0), &box_inertia));
+        // Meshes without volume
+        assert!(std::panic::catch_unwind(|| mesh_centroid(&[])).is_err());
+        assert!(std::panic::catch_unwind(|| mesh_inertia(&mesh[..1], 12.0)).is_err());
+
+        // Tetrahedron, compared with the mesh of its faces
+        let tetrahedron: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(1.0, 0.0, 0.0),
+            &Vector3::new(3.0, 1.0, 0.0),
+            &Vector3::new(1.0, 2.0, 1.0),
+            &Vector3::new(2.0, 1.0, 4.0),
+        );
+        let faces: Vec<Triangle3> = (0..4).map(|i| {
+            let [a, b, c] = tetrahedron.face(i);
+            let face: Triangle3 = Triangle3::new(&a, &b, &c);
+            if face.plane.n.dot(&tetrahedron.face_normal(i)) > 0. { face } else { Triangle3::new(&a, &c, &b) }
+        }).collect();
+        assert!((mesh_volume(&faces) - tetrahedron.volume()).abs() < 1e-9);
+        assert!((mesh_area(&faces) - tetrahedron.area()).abs() < 1e-9);
+        assert!(mesh_centroid(&faces).distance_to(tetrahedron.centroid()) < 1e-9);
+        assert!(same(&mesh_inertia(&faces, 3.0), &tetrahedron.inertia(3.0)));
+        // Regular tetrahedron: the inertia is isotropic, m * edge^2 / 20
+        let regular: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(1.0, 1.0, 1.0),
+            &Vector3::new(1.0, -1.0, -1.0),
+            &Vector3::new(-1.0, 1.0, -1.0),
+            &Vector3::new(-1.0, -1.0, 1.0),
+        );
+        assert!(same(&regular.inertia(5.0), &[[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 2.0]]));
+    }
+
+    #[test]
+    fn transform_tests() {
+        use std::f64::consts::PI;
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+
+        // Composition and inverse
+        let rotation: Transform = Transform::rotation(&Vector3::new(0.0, 0.0, 2.0), PI / 2.0);
+        let translation: Transform = Transform::translation(&Vector3::new(1.0, 2.0, 3.0));
+        let t: Transform = rotation.then(&translation);
+        let p: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+        assert!(close(&t.transform_point(&p), &Vector3::new(1.0, 3.0, 3.0)));
+        assert!(close(&t.transform_vector(&p), &Vector3::new(0.0, 1.0, 0.0)));
+        assert!(close(&t.inverse().transform_point(&Vector3::new(1.0, 3.0, 3.0)), &p));
+        assert!(close(&translation.then(&rotation).transform_point(&p), &Vector3::new(-2.0, 2.0, 3.0)));
+        assert!(close(&Transform::identity().transform_point(&p), &p));
+
+        let general: Transform = Transform::from_matrix(&[
+            [2.0, 1.0, 0.0, 1.0],
+            [0.0, 1.0, 3.0, -2.0],
+            [1.0, 0.0, 1.0, 0.5],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let q: Vector3 = Vector3::new(0.3, -1.2, 4.0);
+        assert!(close(&general.inverse().transform_point(&general.transform_point(&q)), &q));
+        assert!(close(&general.then(&general.inverse()).transform_point(&q), &q));
+        assert_eq!(general.matrix()[1][2], 3.0);
+
+        // Normals under a non-uniform scale stay perpendicular to the surface
+        let scale: Transform = Transform::scale(&Vector3::new(2.0, 1.0, 1.0));
+        assert!(close(&scale.transform_normal(&Vector3::new(1.0, 1.0, 0.0)), &(Vector3::new(1.0, 2.0, 0.0) / 5.0_f64.sqrt())));
+        assert_eq!(scale.normal_matrix(), [[0.5, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        let rotation_normals: [[f64; 3]; 3] = rotation.normal_matrix();
+        assert!((0..3).all(|i| (0..3).all(|j| (rotation_normals[i][j] - rotation.matrix()[i][j]).abs() < 1e-12)));
+
+        // Lines keep their lambdas
+        let line: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 1.0, 0.0));
+        let moved: Line3 = line.transform(&scale.then(&translation));
+        assert!(close(&moved.calc_point(2.0), &scale.then(&translation).transform_point(&line.calc_point(2.0))));
+        assert!((moved.qa - 5.0).abs() < 1e-12);
+
+        // Planes recalculate their independent term
+        let plane: Plane = Plane::new(&Vector3::new(0.0, 0.0, 1.0), &Vector3::new(0.0, 0.0, 1.0));
+        let moved: Plane = plane.transform(&Transform::translation(&Vector3::new(5.0, 0.0, 2.0)));
+        assert!((moved.d + 3.0).abs() < 1e-12 && close(&moved.n, &plane.n));
+        let tilted: Plane = Plane::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 1.0, 0.0)).transform(&scale);
+        assert!(tilted.dist_point(&Vector3::new(2.0, -1.0, 0.0)) < 1e-12);
+
+        // Spheres: non-uniform scales use the largest factor
+        let sphere: Sphere = Sphere::new(&Vector3::new(1.0, 0.0, 0.0), 1.0);
+        let moved: Sphere = sphere.transform(&Transform::scale(&Vector3::new(1.0, -3.0, 2.0)));
+        assert!((moved.r - 3.0).abs() < 1e-9);
+        let skewed: Transform = Transform::rotation(&Vector3::new(1.0, 1.0, 1.0), 1.0).then(&Transform::scale(&Vector3::new(2.0, 0.5, 1.0)));
+        assert!((skewed.max_scale() - 2.0).abs() < 1e-9);
+        assert!((sphere.transform(&rotation).r - 1.0).abs() < 1e-9);
+
+        // Triangles, tetrahedrons, polygons, boxes, capsules and hulls
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(1.0, 0.0, 0.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+        );
+        let moved: Triangle3 = triangle.transform(&Transform::rotation(&Vector3::new(1.0, 0.0, 0.0), PI / 2.0));
+        assert!(close(&moved.plane.n, &Vector3::new(0.0, -1.0, 0.0)) && close(&moved.c, &Vector3::new(0.0, 0.0, 1.0)));
+        let tetrahedron: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(1.0, 0.0, 0.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+            &Vector3::new(0.0, 0.0, 1.0),
+        );
+        assert!((tetrahedron.transform(&Transform::scale(&Vector3::new(2.0, 3.0, -1.0))).volume() - 1.0).abs() < 1e-12);
+        let polygon: Polygon3 = Polygon3::new(&[
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        ]);
+        assert!((polygon.transform(&scale.then(&rotation)).area() - 2.0).abs() < 1e-12);
+        let aabb: Aabb = Aabb::new(&Vector3::new(-1.0, -1.0, 0.0), &Vector3::new(1.0, 1.0, 1.0));
+        let rotated: Aabb = aabb.transform(&Transform::rotation(&Vector3::new(0.0, 0.0, 1.0), PI / 4.0));
+        assert!(close(&rotated.max, &Vector3::new(2.0_f64.sqrt(), 2.0_f64.sqrt(), 1.0)));
+        let capsule: Capsule = Capsule::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 1.0), 0.5).transform(&scale);
+        assert!((capsule.r - 1.0).abs() < 1e-12);
+        let hull: ConvexHull = ConvexHull::new(&aabb.vertices()).transform(&translation);
+        assert!(close(&hull.points[0], &Vector3::new(0.0, 1.0, 3.0)));
+
+        // Oriented boxes: rigid transforms are exact, and non-uniform scales contain the transformed box
+        let obb: Obb = Obb::from(aabb);
+        let quarter: Transform = Transform::rotation(&Vector3::new(0.0, 0.0, 1.0), PI / 4.0).then(&translation);
+        let moved: Obb = obb.transform(&quarter);
+        assert!(close(&moved.c, &quarter.transform_point(&obb.c)) && close(&moved.half_extents, &obb.half_extents));
+        assert!(close(&moved.axes[0], &(Vector3::new(1.0, 1.0, 0.0) / 2.0_f64.sqrt())));
+        assert!(obb.vertices().iter().all(|p| moved.signed_dist_point(&quarter.transform_point(p)).abs() < 1e-9));
+        let diagonal: Obb = obb.transform(&Transform::rotation(&Vector3::new(0.0, 0.0, 1.0), PI / 4.0));
+        let stretched: Obb = diagonal.transform(&scale);
+        assert!(diagonal.vertices().iter().all(|p| stretched.signed_dist_point(&scale.transform_point(p)) < 1e-9));
+        assert!(close(&stretched.axes[0], &(Vector3::new(2.0, 1.0, 0.0) / 5.0_f64.sqrt())));
+
+        // Circles: rigid transforms are exact, and non-uniform scales use the largest factor
+        let circle: Circle3 = Circle3::new(&Vector3::new(1.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 1.0), 1.0);
+        let moved: Circle3 = circle.transform(&Transform::rotation(&Vector3::new(1.0, 0.0, 0.0), PI / 2.0));
+        assert!(close(&moved.n, &Vector3::new(0.0, -1.0, 0.0)) && close(&moved.c, &circle.c) && (moved.r - 1.0).abs() < 1e-12);
+        let ellipse: Circle3 = circle.transform(&scale);
+        assert!(close(&ellipse.c, &Vector3::new(2.0, 0.0, 0.0)) && close(&ellipse.n, &circle.n) && (ellipse.r - 2.0).abs() < 1e-12);
+        assert!(circle.sample(16).iter().all(|p| scale.transform_point(p).distance_to(ellipse.c) <= ellipse.r + 1e-9));
+
+        // Invalid transforms
+        assert!(std::panic::catch_unwind(|| Transform::scale(&Vector3::new(1.0, 0.0, 1.0))).is_err());
+        assert!(std::panic::catch_unwind(|| Transform::rotation(&Vector3::new(0.0, 0.0, 0.0), 1.0)).is_err());
+        assert!(std::panic::catch_unwind(|| Transform::from_matrix(&[
+            [1.0, 2.0, 3.0, 0.0],
+            [2.0, 4.0, 6.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])).is_err());
+        assert!(std::panic::catch_unwind(|| Transform::from_matrix(&[
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0, 1.0],
+        ])).is_err());
+    }
+
+    #[test]
+    fn quaternion_tests() {
+        use std::f64::consts::PI;
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+        // Same rotation (q and -q are the same one)
+        let same = |q1: &Quaternion, q2: &Quaternion| (q1.dot(q2).abs() - 1.0).abs() < 1e-9;
+        let x: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+        let y: Vector3 = Vector3::new(0.0, 1.0, 0.0);
+        let z: Vector3 = Vector3::new(0.0, 0.0, 1.0);
+
+        // Axis-angle
+        let q: Quaternion = Quaternion::from_axis_angle(&(z * 3.0), PI / 2.0);
+        assert!(close(&q.rotate(&x), &y));
+        let (axis, angle) = Quaternion::from_axis_angle(&Vector3::new(1.0, 2.0, 2.0), 2.5).to_axis_angle();
+        assert!(close(&axis, &(Vector3::new(1.0, 2.0, 2.0) / 3.0)) && (angle - 2.5).abs() < 1e-9);
+        assert_eq!(Quaternion::identity().to_axis_angle().1, 0.0);
+        assert!(std::panic::catch_unwind(|| Quaternion::from_axis_angle(&Vector3::new(0.0, 0.0, 0.0), 1.0)).is_err());
+
+        // Composition, inverse and conjugate
+        let qx: Quaternion = Quaternion::from_axis_angle(&x, PI / 2.0);
+        assert!(close(&q.then(&qx).rotate(&x), &z));
+        assert!(close(&qx.then(&q).rotate(&x), &y));
+        assert!(same(&q.multiply(&q.inverse()), &Quaternion::identity()));
+        assert!(close(&q.conjugate().rotate(&y), &x));
+        let scaled: Quaternion = Quaternion::new(2.0, 0.0, 0.0, 2.0);
+        assert!((scaled.magnitude() - 8.0_f64.sqrt()).abs() < 1e-12 && same(&scaled.normalize(), &q));
+        assert!(close(&scaled.rotate(&x), &y));
+
+        // Euler angles: roll around X, then pitch around Y, then yaw around Z
+        assert!(same(&Quaternion::from_euler(0.0, 0.0, PI / 2.0), &q));
+        let (roll, pitch, yaw) = (0.3, -0.4, 1.2);
+        let euler: Quaternion = Quaternion::from_euler(roll, pitch, yaw);
+        let composed: Quaternion = Quaternion::from_axis_angle(&x, roll)
+            .then(&Quaternion::from_axis_angle(&y, pitch))
+            .then(&Quaternion::from_axis_angle(&z, yaw));
+        assert!(same(&euler, &composed));
+        let angles: (f64, f64, f64) = euler.to_euler();
+        assert!((angles.0 - roll).abs() < 1e-9 && (angles.1 - pitch).abs() < 1e-9 && (angles.2 - yaw).abs() < 1e-9);
+
+        // Matrices (including half turns, where the trace is negative)
+        for q in [euler, q, Quaternion::from_axis_angle(&x, PI), Quaternion::from_axis_angle(&y, PI), Quaternion::from_axis_angle(&Vector3::new(1.0, 1.0, 3.0), PI)] {
+            assert!(same(&Quaternion::from_matrix(&q.to_matrix()), &q));
+            let m: [[f64; 4]; 4] = Transform::from(q).matrix();
+            let r: [[f64; 3]; 3] = q.to_matrix();
+            assert!((0..3).all(|i| (0..3).all(|j| (m[i][j] - r[i][j]).abs() < 1e-12)));
+            let v: Vector3 = Vector3::new(0.3, -2.0, 1.5);
+            assert!(close(&Transform::from(q).transform_point(&v), &q.rotate(&v)));
+        }
+
+        // Interpolation
+        let half: Quaternion = Quaternion::identity().slerp(&q, 0.5);
+        assert!(same(&half, &Quaternion::from_axis_angle(&z, PI / 4.0)));
+        assert!(same(&Quaternion::identity().slerp(&q, 0.0), &Quaternion::identity()) && same(&Quaternion::identity().slerp(&q, 1.0), &q));
+        assert!(same(&Quaternion::identity().nlerp(&q, 0.5), &half));
+        let third: Quaternion = Quaternion::identity().slerp(&q, 1.0 / 3.0);
+        assert!(close(&third.rotate(&x), &Vector3::new((PI / 6.0).cos(), (PI / 6.0).sin(), 0.0)));
+        // Shortest path: -q is the same rotation as q
+        let opposite: Quaternion = Quaternion::new(-q.w, -q.x, -q.y, -q.z);
+        assert!(same(&Quaternion::identity().slerp(&opposite, 0.5), &half));
+
+        // Shapes rotated directly
+        let triangle: Triangle3 = Triangle3::new(&x, &y, &z);
+        let rotated: Triangle3 = triangle.rotate(&q);
+        assert!(close(&rotated.a, &y) && close(&rotated.b, &(x * -1.0)) && close(&rotated.c, &z));
+        let sphere: Sphere = Sphere::new(&x, 1.0).rotate(&q);
+        assert!(close(&sphere.c, &y) && (sphere.r - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn transformed_tests() {
+        use std::f64::consts::PI;
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+
+        // Ellipsoid: unit sphere scaled along X and moved
+        let ellipsoid: Transformed<Sphere> = Transformed::new(
+            Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0),
+            &Transform::scale(&Vector3::new(2.0, 1.0, 1.0)).then(&Transform::translation(&Vector3::new(5.0, 0.0, 0.0))),
+        );
+        let line: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 0.0, 0.0));
+        let mut lambdas: Vec<f64> = ellipsoid.intersects(&line).iter().copied().collect();
+        lambdas.sort_by(f64::total_cmp);
+        assert!((lambdas[0] - 3.0).abs() < 1e-9 && (lambdas[1] - 7.0).abs() < 1e-9);
+        // The lambdas are valid in world space, even with a line that is not normalized
+        let long: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(2.0, 0.0, 0.0));
+        assert!((ellipsoid.closest_intersection(&long).unwrap().lambda - 1.5).abs() < 1e-9);
+        assert!(ellipsoid.closest_intersection(&Line3::new(&Vector3::new(0.0, 1.5, 0.0), &Vector3::new(1.0, 1.5, 0.0))).is_none());
+        // Normals stay perpendicular to the scaled surface
+        assert!(close(&ellipsoid.normal(&Vector3::new(7.0, 0.0, 0.0)), &Vector3::new(1.0, 0.0, 0.0)));
+        let t: f64 = PI / 3.0;
+        let point: Vector3 = Vector3::new(5.0 + 2.0 * t.cos(), t.sin(), 0.0);
+        assert!(close(&ellipsoid.normal(&point), &Vector3::new(t.cos() / 2.0, t.sin(), 0.0).normalize()));
+
+        // Instanced triangle: the barycentric coordinates don´t change
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(1.0, 0.0, 0.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+        );
+        let instance: Transformed<Triangle3> = Transformed::new(
+            triangle,
+            &Transform::rotation(&Vector3::new(1.0, 0.0, 0.0), PI / 2.0).then(&Transform::translation(&Vector3::new(0.0, 0.0, 10.0))),
+        );
+        let ray: Line3 = Line3::new(&Vector3::new(0.25, 5.0, 10.5), &Vector3::new(0.25, 4.0, 10.5));
+        let intersection: Intersection = instance.closest_intersection(&ray).unwrap();
+        assert!((intersection.lambda - 5.0).abs() < 1e-9);
+        assert!(close(&intersection.barycentric.unwrap(), &Vector3::new(0.25, 0.25, 0.5)));
+        assert!(close(&instance.normal(&ray.calc_point(5.0)), &Vector3::new(0.0, -1.0, 0.0)));
+
+        // Nested transforms compose, and the support function works with GJK
+        let moved: Transformed<Triangle3> = instance.transform(&Transform::translation(&Vector3::new(0.0, 0.0, -10.0)));
+        assert!((moved.closest_intersection(&Line3::new(&Vector3::new(0.25, 5.0, 0.5), &Vector3::new(0.25, 4.0, 0.5))).unwrap().lambda - 5.0).abs() < 1e-9);
+        let separation: Separation = gjk_distance(&ellipsoid, &Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0)).unwrap();
+        assert!((separation.distance - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn motion_tests() {
+        use std::f64::consts::PI;
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+
+        // Bounding boxes of the shapes
+        let sphere: Sphere = Sphere::new(&Vector3::new(1.0, 2.0, 3.0), 1.0);
+        let aabb: Aabb = sphere.bounding_box();
+        assert!(close(&aabb.min, &Vector3::new(0.0, 1.0, 2.0)) && close(&aabb.max, &Vector3::new(2.0, 3.0, 4.0)));
+        let capsule: Capsule = Capsule::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(4.0, 0.0, 0.0), 0.5);
+        let aabb: Aabb = capsule.bounding_box();
+        assert!(close(&aabb.min, &Vector3::new(-0.5, -0.5, -0.5)) && close(&aabb.max, &Vector3::new(4.5, 0.5, 0.5)));
+        let obb: Obb = Obb::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 1.0, 0.0), &Vector3::new(-1.0, 1.0, 0.0), &Vector3::new(1.0, 1.0, 1.0));
+        let aabb: Aabb = obb.bounding_box();
+        assert!(close(&aabb.max, &Vector3::new(2.0_f64.sqrt(), 2.0_f64.sqrt(), 1.0)));
+
+        // Moving sphere from (0, 0, 0) at time 0 to (10, 0, 0) at time 1
+        let moving: MovingSphere = MovingSphere::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(10.0, 0.0, 0.0), 0.0, 1.0, 1.0);
+        let ray: Line3 = Line3::new(&Vector3::new(5.0, -5.0, 0.0), &Vector3::new(5.0, -4.0, 0.0));
+        assert!(moving.closest_intersection_at(&ray, 0.0).is_none());
+        assert!((moving.closest_intersection_at(&ray, 0.5).unwrap().lambda - 4.0).abs() < 1e-9);
+        assert!(moving.closest_intersection_at(&ray, 1.0).is_none());
+        assert_eq!(moving.intersects_at(&ray, 0.5).iter().count(), 2);
+        assert!(close(&moving.normal_at(&Vector3::new(5.0, -1.0, 0.0), 0.5), &Vector3::new(0.0, -1.0, 0.0)));
+        // Out of the motion interval the sphere stays at the ends
+        assert!(close(&moving.at(-1.0).c, &Vector3::new(0.0, 0.0, 0.0)) && close(&moving.at(2.0).c, &Vector3::new(10.0, 0.0, 0.0)));
+        let aabb: Aabb = moving.bounding_box();
+        assert!(close(&aabb.min, &Vector3::new(-1.0, -1.0, -1.0)) && close(&aabb.max, &Vector3::new(11.0, 1.0, 1.0)));
+
+        // Keyframed triangle: moved and rotated a quarter turn around Z between times 0 and 2
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(1.0, 0.0, 0.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+        );
+        let one: Vector3 = Vector3::new(1.0, 1.0, 1.0);
+        let keyframed: Keyframed<Triangle3> = Keyframed::new(triangle, &[
+            Keyframe::new(2.0, &Vector3::new(0.0, 0.0, 4.0), &Quaternion::from_axis_angle(&Vector3::new(0.0, 0.0, 1.0), PI / 2.0), &one),
+            Keyframe::new(0.0, &Vector3::new(0.0, 0.0, 0.0), &Quaternion::identity(), &one),
+        ]);
+        assert_eq!(keyframed.keyframes()[0].time, 0.0);
+        assert!(close(&keyframed.transform_at(1.0).transform_point(&Vector3::new(1.0, 0.0, 0.0)), &Vector3::new(0.5_f64.sqrt(), 0.5_f64.sqrt(), 2.0)));
+        let ray: Line3 = Line3::new(&Vector3::new(0.1, 0.3, 5.0), &Vector3::new(0.1, 0.3, 4.0));
+        assert!((keyframed.closest_intersection_at(&ray, 0.0).unwrap().lambda - 5.0).abs() < 1e-9);
+        assert!((keyframed.closest_intersection_at(&ray, 1.0).unwrap().lambda - 3.0).abs() < 1e-9);
+        // At the end the triangle is on the side of negative X
+        assert!(keyframed.closest_intersection_at(&ray, 2.0).is_none());
+        let ray: Line3 = Line3::new(&Vector3::new(-0.25, 0.25, 5.0), &Vector3::new(-0.25, 0.25, 4.0));
+        assert!((keyframed.closest_intersection_at(&ray, 3.0).unwrap().lambda - 1.0).abs() < 1e-9);
+        assert!(close(&keyframed.normal_at(&Vector3::new(-0.25, 0.25, 4.0), 2.0), &Vector3::new(0.0, 0.0, 1.0)));
+
+        // The bounding box covers the shape during the whole motion
+        let aabb: Aabb = keyframed.bounding_box();
+        for i in 0..=20 {
+            let t: Transform = keyframed.transform_at(i as f64 / 10.0);
+            for v in [triangle.a, triangle.b, triangle.c] {
+                assert!(aabb.contains(&t.transform_point(&v)));
+            }
+        }
+    }
+
+    #[test]
+    fn scene_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+
+        let mut scene: Scene = Scene::new();
+        assert!(scene.is_empty());
+        scene.add(1, Sphere::new(&Vector3::new(0.0, 0.0, 10.0), 1.0));
+        scene.add(2, Plane::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(0.0, 0.0, 1.0)));
+        scene.add(3, Triangle3::new(
+            &Vector3::new(-1.0, -1.0, 5.0),
+            &Vector3::new(1.0, -1.0, 5.0),
+            &Vector3::new(0.0, 1.0, 5.0),
+        ));
+        assert_eq!(scene.len(), 3);
+        assert!(scene.get(3).is_some() && scene.get(4).is_none());
+
+        // Looking down from above: the sphere is hit first
+        let down: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 20.0), &Vector3::new(0.0, 0.0, 19.0));
+        let (intersection, id) = scene.closest_hit(&down).unwrap();
+        assert_eq!(id, 1);
+        assert!((intersection.lambda - 9.0).abs() < 1e-9);
+        assert_eq!(scene.intersects(&down).iter().count(), 4);
+        // Below the sphere: the triangle, with its barycentric coordinates
+        let below: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 8.0), &Vector3::new(0.0, 0.0, 7.0));
+        let (intersection, id) = scene.closest_hit(&below).unwrap();
+        assert_eq!(id, 3);
+        assert!((intersection.lambda - 3.0).abs() < 1e-9 && intersection.barycentric.is_some());
+        // Away from the sphere and the triangle: the plane
+        let away: Line3 = Line3::new(&Vector3::new(5.0, 5.0, 8.0), &Vector3::new(5.0, 5.0, 7.0));
+        assert_eq!(scene.closest_hit(&away).unwrap().1, 2);
+        assert!((scene.closest_intersection(&away).unwrap().lambda - 8.0).abs() < 1e-9);
+        assert!(scene.closest_intersection(&Line3::new(&Vector3::new(5.0, 5.0, 8.0), &Vector3::new(5.0, 5.0, 9.0))).is_none());
+
+        // The normal is the one of the closest member
+        assert!(close(&scene.normal(&Vector3::new(0.0, 1.0, 10.0)), &Vector3::new(0.0, 1.0, 0.0)));
+        assert!(close(&scene.normal(&Vector3::new(5.0, 5.0, 0.0)), &Vector3::new(0.0, 0.0, 1.0)));
+        assert!(close(&scene.closest_point(&Vector3::new(0.0, 3.0, 10.0)), &Vector3::new(0.0, 1.0, 10.0)));
+
+        // Nested scenes report the ID of the leaf member that was hit
+        let mut world: Scene = Scene::new();
+        world.add_scene(10, scene);
+        world.add(20, Transformed::new(
+            Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0),
+            &Transform::translation(&Vector3::new(0.0, 0.0, 30.0)),
+        ));
+        assert_eq!(world.len(), 2);
+        assert!(world.get(10).is_some() && world.get(3).is_some());
+        assert_eq!(world.closest_hit(&down).unwrap().1, 1);
+        assert_eq!(world.closest_hit(&below).unwrap().1, 3);
+        let up: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 20.0), &Vector3::new(0.0, 0.0, 21.0));
+        let (intersection, id) = world.closest_hit(&up).unwrap();
+        assert_eq!(id, 20);
+        assert!((intersection.lambda - 9.0).abs() < 1e-9);
+        assert!(close(&world.normal(&up.calc_point(intersection.lambda)), &Vector3::new(0.0, 0.0, -1.0)));
+        // The IDs must be unique in the whole tree
+        let mut nested: Scene = Scene::new();
+        nested.add(1, Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0));
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| world.add_scene(30, nested))).is_err());
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| world.add(2, Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0)))).is_err());
+    }
+
+    #[test]
+    fn shape_kind_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let triangle: Triangle3 = Triangle3::new(
+            &Vector3::new(-1.0, -1.0, 5.0),
+            &Vector3::new(1.0, -1.0, 5.0),
+            &Vector3::new(0.0, 1.0, 5.0),
+        );
+        let aabb: Aabb = Aabb::new(&Vector3::new(-1.0, -1.0, -6.0), &Vector3::new(1.0, 1.0, -4.0));
+        let shapes: Vec<ShapeKind> = vec![
+            sphere.into(),
+            triangle.into(),
+            aabb.into(),
+            Plane::new(&Vector3::new(0.0, 0.0, 10.0), &Vector3::new(0.0, 0.0, -1.0)).into(),
+            Tetrahedron::new(
+                &Vector3::new(0.0, 0.0, 20.0),
+                &Vector3::new(1.0, 0.0, 20.0),
+                &Vector3::new(0.0, 1.0, 20.0),
+                &Vector3::new(0.0, 0.0, 21.0),
+            ).into(),
+            Polygon3::new(&[
+                Vector3::new(-1.0, -1.0, -10.0),
+                Vector3::new(1.0, -1.0, -10.0),
+                Vector3::new(1.0, 1.0, -10.0),
+                Vector3::new(-1.0, 1.0, -10.0),
+            ]).into(),
+        ];
+        assert!(matches!(shapes[0], ShapeKind::Sphere(_)) && matches!(shapes[5], ShapeKind::Polygon3(_)));
+
+        // The dispatch gives the same results as the concrete shapes
+        let line: Line3 = Line3::new(&Vector3::new(0.0, 0.0, -20.0), &Vector3::new(0.0, 0.0, -19.0));
+        let lambdas: Vec<f64> = shapes.iter().map(|s| s.closest_intersection(&line).unwrap().lambda).collect();
+        assert!((lambdas[0] - sphere.closest_intersection(&line).unwrap().lambda).abs() < 1e-12);
+        assert!((lambdas[1] - 25.0).abs() < 1e-9 && (lambdas[2] - 14.0).abs() < 1e-9 && (lambdas[3] - 30.0).abs() < 1e-9);
+        assert!((lambdas[4] - 40.0).abs() < 1e-9 && (lambdas[5] - 10.0).abs() < 1e-9);
+        assert!(shapes[1].closest_intersection(&line).unwrap().barycentric.is_some());
+        assert_eq!(shapes[0].intersects(&line).iter().count(), 2);
+        assert!(close(&shapes[0].normal(&Vector3::new(0.0, 1.0, 0.0)), &sphere.normal(&Vector3::new(0.0, 1.0, 0.0))));
+        assert!(close(&shapes[2].closest_point(&Vector3::new(0.0, 0.0, 0.0)), &Vector3::new(0.0, 0.0, -4.0)));
+        assert!((shapes[3].dist_point(&Vector3::new(3.0, 3.0, 3.0)) - 7.0).abs() < 1e-9);
+
+        // The enum can be used in a scene
+        let mut scene: Scene = Scene::new();
+        for (id, shape) in shapes.into_iter().enumerate() {
+            scene.add(id as u64, shape);
+        }
+        assert_eq!(scene.closest_hit(&line).unwrap().1, 5);
+    }
+
+    #[test]
+    fn hits_tests() {
+        // Sorted insertion, in place
+        let mut hits: Hits = Hits::new();
+        assert!(hits.is_empty() && hits.first_positive().is_none());
+        hits.push(3.0);
+        hits.push(-1.0);
+        assert_eq!(hits.as_slice(), &[-1.0, 3.0]);
+        assert_eq!(hits.first_positive(), Some(3.0));
+        assert_eq!(hits.into_iter().collect::<Vec<f64>>(), vec![-1.0, 3.0]);
+        assert!(std::panic::catch_unwind(move || hits.push(5.0)).is_err());
+
+        // The same lambdas as the list based API, for all shapes
+        let sorted = |list: List<f64>| {
+            let mut lambdas: Vec<f64> = list.iter().copied().collect();
+            lambdas.sort_by(f64::total_cmp);
+            lambdas
+        };
+        let shapes: Vec<ShapeKind> = vec![
+            Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0).into(),
+            Plane::new(&Vector3::new(0.0, 0.0, 10.0), &Vector3::new(0.0, 0.0, -1.0)).into(),
+            Triangle3::new(
+                &Vector3::new(-1.0, -1.0, 5.0),
+                &Vector3::new(1.0, -1.0, 5.0),
+                &Vector3::new(0.0, 1.0, 5.0),
+            ).into(),
+            Tetrahedron::new(
+                &Vector3::new(-1.0, -1.0, 20.0),
+                &Vector3::new(2.0, -1.0, 20.0),
+                &Vector3::new(-1.0, 2.0, 20.0),
+                &Vector3::new(-1.0, -1.0, 23.0),
+            ).into(),
+            Polygon3::new(&[
+                Vector3::new(-1.0, -1.0, -10.0),
+                Vector3::new(1.0, -1.0, -10.0),
+                Vector3::new(1.0, 1.0, -10.0),
+                Vector3::new(-1.0, 1.0, -10.0),
+            ]).into(),
+            Aabb::new(&Vector3::new(-1.0, -1.0, -6.0), &Vector3::new(1.0, 1.0, -4.0)).into(),
+        ];
+        let lines: [Line3; 3] = [
+            Line3::new(&Vector3::new(0.0, 0.0, -20.0), &Vector3::new(0.0, 0.0, -19.0)),
+            Line3::new(&Vector3::new(0.5, 0.0, 30.0), &Vector3::new(0.4, 0.1, 28.0)),
+            Line3::new(&Vector3::new(5.0, 5.0, 0.0), &Vector3::new(6.0, 5.0, 0.0)),
+        ];
+        for shape in shapes.iter() {
+            for line in lines.iter() {
+                let hits: Hits = shape.hits(line);
+                let expected: Vec<f64> = sorted(shape.intersects(line));
+                assert_eq!(hits.len(), expected.len());
+                for (lambda, other) in hits.iter().zip(expected.iter()) {
+                    assert!((lambda - other).abs() < 1e-9);
+                }
+            }
+        }
+        let hits: Hits = shapes[0].hits(&lines[0]);
+        assert_eq!(hits.as_slice(), &[19.0, 21.0]);
+        assert_eq!(shapes[2].hits(&lines[2]).len(), 0);
+
+        // Instanced shapes keep the lambdas in world space
+        let moved: Transformed<Sphere> = Transformed::new(
+            Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0),
+            &Transform::translation(&Vector3::new(0.0, 0.0, 5.0)),
+        );
+        assert_eq!(moved.hits(&lines[0]).first_positive(), Some(24.0));
+    }
+
+    #[test]
+    fn interval_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+        let x: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+        let minus_x: Vector3 = Vector3::new(-1.0, 0.0, 0.0);
+
+        // Sphere: entry before exit, whatever the direction of the line
+        let sphere: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let forward: Line3 = Line3::new(&Vector3::new(-5.0, 0.0, 0.0), &Vector3::new(-4.0, 0.0, 0.0));
+        let intervals: Vec<Interval> = sphere.intervals(&forward);
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].entry - 4.0).abs() < 1e-9 && (intervals[0].exit - 6.0).abs() < 1e-9);
+        assert!(close(&intervals[0].entry_normal, &minus_x) && close(&intervals[0].exit_normal, &x));
+        assert!((intervals[0].length() - 2.0).abs() < 1e-9 && intervals[0].contains(5.0) && !intervals[0].contains(7.0));
+        let listed: Vec<f64> = sphere.intersects(&forward).iter().copied().collect();
+        assert!((listed[0] - 4.0).abs() < 1e-9 && (listed[1] - 6.0).abs() < 1e-9);
+        let backward: Line3 = Line3::new(&Vector3::new(5.0, 0.0, 0.0), &Vector3::new(4.0, 0.0, 0.0));
+        let intervals: Vec<Interval> = sphere.intervals(&backward);
+        assert!((intervals[0].entry - 4.0).abs() < 1e-9 && close(&intervals[0].entry_normal, &x));
+        // Grazing and missing lines have no interval
+        assert!(sphere.intervals(&Line3::new(&Vector3::new(-5.0, 1.0, 0.0), &Vector3::new(-4.0, 1.0, 0.0))).is_empty());
+        assert!(sphere.intervals(&Line3::new(&Vector3::new(-5.0, 2.0, 0.0), &Vector3::new(-4.0, 2.0, 0.0))).is_empty());
+
+        // Boxes: the normals are the ones of the faces crossed (a line that touches just an edge has no interval)
+        let aabb: Aabb = Aabb::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(2.0, 2.0, 2.0));
+        let diagonal: Line3 = Line3::new(&Vector3::new(1.0, -1.0, 1.0), &Vector3::new(2.0, 0.0, 1.0));
+        assert!(aabb.intervals(&diagonal).is_empty());
+        assert!(aabb.intervals(&Line3::new(&Vector3::new(1.0, 1.0, 1.0), &Vector3::new(1.0, 1.0, 3.0))).len() == 1);
+        let inside: Line3 = Line3::new(&Vector3::new(1.0, 1.0, 1.0), &Vector3::new(1.0, 2.0, 1.0));
+        let intervals: Vec<Interval> = aabb.intervals(&inside);
+        assert!((intervals[0].entry + 1.0).abs() < 1e-9 && (intervals[0].exit - 1.0).abs() < 1e-9);
+        assert!(close(&intervals[0].entry_normal, &Vector3::new(0.0, -1.0, 0.0)) && close(&intervals[0].exit_normal, &Vector3::new(0.0, 1.0, 0.0)));
+        let obb: Obb = Obb::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 1.0, 0.0), &Vector3::new(-1.0, 1.0, 0.0), &Vector3::new(1.0, 1.0, 1.0));
+        let intervals: Vec<Interval> = obb.intervals(&forward);
+        assert!((intervals[0].entry - (5.0 - 2.0_f64.sqrt())).abs() < 1e-9 && (intervals[0].exit - (5.0 + 2.0_f64.sqrt())).abs() < 1e-9);
+        assert!(close(&intervals[0].exit_normal, &Vector3::new(1.0, 1.0, 0.0).normalize()) || close(&intervals[0].exit_normal, &Vector3::new(1.0, -1.0, 0.0).normalize()));
+
+        // Tetrahedron
+        let tetrahedron: Tetrahedron = Tetrahedron::new(
+            &Vector3::new(0.0, 0.0, 0.0),
+            &Vector3::new(2.0, 0.0, 0.0),
+            &Vector3::new(0.0, 2.0, 0.0),
+            &Vector3::new(0.0, 0.0, 2.0),
+        );
+        let up: Line3 = Line3::new(&Vector3::new(0.5, 0.5, -1.0), &Vector3::new(0.5, 0.5, 0.0));
+        let intervals: Vec<Interval> = tetrahedron.intervals(&up);
+        assert!((intervals[0].entry - 1.0).abs() < 1e-9 && (intervals[0].exit - 2.0).abs() < 1e-9);
+        assert!(close(&intervals[0].entry_normal, &Vector3::new(0.0, 0.0, -1.0)));
+        assert!(close(&intervals[0].exit_normal, &Vector3::new(1.0, 1.0, 1.0).normalize()));
+
+        // Transformed shapes keep the lambdas in world space
+        let ellipsoid: Transformed<Sphere> = Transformed::new(sphere, &Transform::scale(&Vector3::new(2.0, 1.0, 1.0)));
+        let intervals: Vec<Interval> = ellipsoid.intervals(&forward);
+        assert!((intervals[0].entry - 3.0).abs() < 1e-9 && (intervals[0].exit - 7.0).abs() < 1e-9);
+        assert!(close(&intervals[0].exit_normal, &x));
+
+        // Closed mesh of two separated cubes, with a line through the shared edges of their triangles
+        let v: [Vector3; 8] = aabb.vertices();
+        let quads: [[usize; 4]; 6] = [[0, 4, 6, 2], [1, 3, 7, 5], [0, 1, 5, 4], [2, 6, 7, 3], [0, 2, 3, 1], [4, 5, 7, 6]];
+        let cube: Vec<Triangle3> = quads.iter().flat_map(|&[p, q, r, s]| [
+            Triangle3::new(&v[p], &v[q], &v[r]),
+            Triangle3::new(&v[p], &v[r], &v[s]),
+        ]).collect();
+        let moved: Vec<Triangle3> = cube.iter().map(|t| t.transform(&Transform::translation(&Vector3::new(4.0, 0.0, 0.0)))).collect();
+        let mesh: Vec<Triangle3> = cube.iter().chain(moved.iter()).copied().collect();
+        let line: Line3 = Line3::new(&Vector3::new(-1.0, 1.0, 1.0), &Vector3::new(0.0, 1.0, 1.0));
+        let intervals: Vec<Interval> = mesh_intervals(&mesh, &line);
+        assert_eq!(intervals.len(), 2);
+        assert!((intervals[0].entry - 1.0).abs() < 1e-9 && (intervals[0].exit - 3.0).abs() < 1e-9);
+        assert!((intervals[1].entry - 5.0).abs() < 1e-9 && (intervals[1].exit - 7.0).abs() < 1e-9);
+        assert!(close(&intervals[1].entry_normal, &minus_x) && close(&intervals[1].exit_normal, &x));
+        // Only the entry is through a shared edge (the diagonal of the bottom face), so it must be counted once
+        let unit: Vec<Triangle3> = cube.iter()
+            .map(|t| t.transform(&Transform::scale(&Vector3::new(0.5, 0.5, 0.5))))
+            .collect();
+        let slanted: Line3 = Line3::new(&Vector3::new(0.3, 0.3, 0.0), &Vector3::new(0.4, 0.3, 1.0));
+        let intervals: Vec<Interval> = mesh_intervals(&unit, &slanted);
+        assert_eq!(intervals.len(), 1);
+        assert!(intervals[0].entry.abs() < 1e-9 && (intervals[0].exit - 1.0).abs() < 1e-9);
+        assert!(close(&intervals[0].entry_normal, &Vector3::new(0.0, 0.0, -1.0)));
+        // The same intervals as the box
+        let inside: Vec<Interval> = mesh_intervals(&cube, &Line3::new(&Vector3::new(0.5, 1.5, 0.5), &Vector3::new(0.5, 1.5, 1.5)));
+        assert!((inside[0].entry + 0.5).abs() < 1e-9 && (inside[0].exit - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn csg_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-9;
+        let x: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+        let minus_x: Vector3 = Vector3::new(-1.0, 0.0, 0.0);
+        let same = |i: &Interval, entry: f64, exit: f64| (i.entry - entry).abs() < 1e-9 && (i.exit - exit).abs() < 1e-9;
+
+        let s1: Sphere = Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let s2: Sphere = Sphere::new(&Vector3::new(1.5, 0.0, 0.0), 1.0);
+        let line: Line3 = Line3::new(&Vector3::new(-5.0, 0.0, 0.0), &Vector3::new(-4.0, 0.0, 0.0));
+
+        // Union: the overlapping intervals are merged
+        let union: Csg<Sphere, Sphere> = Csg::union(s1, s2);
+        let intervals: Vec<Interval> = union.intervals(&line);
+        assert!(intervals.len() == 1 && same(&intervals[0], 4.0, 7.5));
+        assert!(close(&intervals[0].entry_normal, &minus_x) && close(&intervals[0].exit_normal, &x));
+        assert_eq!(union.intersects(&line).iter().count(), 2);
+
+        // Intersection: the lens between both spheres
+        let lens: Csg<Sphere, Sphere> = Csg::intersection(s1, s2);
+        let intervals: Vec<Interval> = lens.intervals(&line);
+        assert!(intervals.len() == 1 && same(&intervals[0], 5.5, 6.0));
+        assert!(close(&intervals[0].entry_normal, &minus_x) && close(&intervals[0].exit_normal, &x));
+        assert!((lens.closest_intersection(&line).unwrap().lambda - 5.5).abs() < 1e-9);
+
+        // Difference: the surface of the subtracted sphere has its normal flipped
+        let bitten: Csg<Sphere, Sphere> = Csg::difference(s1, s2);
+        let intervals: Vec<Interval> = bitten.intervals(&line);
+        assert!(intervals.len() == 1 && same(&intervals[0], 4.0, 5.5));
+        assert!(close(&intervals[0].exit_normal, &x));
+        assert!(close(&bitten.normal(&Vector3::new(0.5, 0.0, 0.0)), &x));
+        assert!(close(&bitten.normal(&Vector3::new(-1.0, 0.0, 0.0)), &minus_x));
+        // The bitten part is not hit
+        let through_bite: Line3 = Line3::new(&Vector3::new(5.0, 0.0, 0.0), &Vector3::new(4.0, 0.0, 0.0));
+        assert!((bitten.closest_intersection(&through_bite).unwrap().lambda - 4.5).abs() < 1e-9);
+
+        // Hollow sphere, seen from its hole: the normal points to the hole
+        let hollow: Csg<Sphere, Sphere> = Csg::difference(s1, Sphere::new(&Vector3::new(0.0, 0.0, 0.0), 0.5));
+        let from_center: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 0.0, 0.0));
+        let intervals: Vec<Interval> = hollow.intervals(&from_center);
+        assert!(intervals.len() == 2 && same(&intervals[0], -1.0, -0.5) && same(&intervals[1], 0.5, 1.0));
+        let intersection: Intersection = hollow.closest_intersection(&from_center).unwrap();
+        assert!((intersection.lambda - 0.5).abs() < 1e-9);
+        assert!(close(&hollow.normal(&from_center.calc_point(intersection.lambda)), &minus_x));
+        assert!(close(&intervals[1].entry_normal, &minus_x));
+
+        // Nested nodes, with boxes and transformed shapes
+        let cut: Csg<Csg<Sphere, Sphere>, Aabb> = Csg::difference(
+            union,
+            Aabb::new(&Vector3::new(-2.0, -2.0, 0.5), &Vector3::new(3.0, 2.0, 2.0)),
+        );
+        let down: Line3 = Line3::new(&Vector3::new(0.0, 0.0, 5.0), &Vector3::new(0.0, 0.0, 4.0));
+        let intersection: Intersection = cut.closest_intersection(&down).unwrap();
+        assert!((intersection.lambda - 4.5).abs() < 1e-9);
+        assert!(close(&cut.normal(&down.calc_point(intersection.lambda)), &Vector3::new(0.0, 0.0, 1.0)));
+        let moved: Csg<Transformed<Sphere>, Sphere> = Csg::union(
+            Transformed::new(s1, &Transform::translation(&Vector3::new(-10.0, 0.0, 0.0))),
+            s1,
+        );
+        let intervals: Vec<Interval> = moved.intervals(&line);
+        assert!(intervals.len() == 2 && same(&intervals[0], -6.0, -4.0) && same(&intervals[1], 4.0, 6.0));
+        assert_eq!(cut.op, CsgOp::Difference);
+
+        // Bounding boxes
+        let aabb: Aabb = Csg::union(s1, s2).bounding_box();
+        assert!(close(&aabb.min, &Vector3::new(-1.0, -1.0, -1.0)) && close(&aabb.max, &Vector3::new(2.5, 1.0, 1.0)));
+        let aabb: Aabb = Csg::intersection(s1, s2).bounding_box();
+        assert!(close(&aabb.max, &Vector3::new(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn sdf_tests() {
+        let close = |a: &Vector3, b: &Vector3| a.distance_to(*b) < 1e-6;
+        let origin: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+        let x: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+
+        // Primitives
+        let sphere: Sphere = Sphere::new(&origin, 1.0);
+        assert!((sphere.distance(&Vector3::new(3.0, 0.0, 0.0)) - 2.0).abs() < 1e-12);
+        assert!(close(&sphere.gradient(&Vector3::new(0.0, 2.0, 0.0)), &Vector3::new(0.0, 1.0, 0.0)));
+        let aabb: Aabb = Aabb::new(&Vector3::new(-1.0, -1.0, -1.0), &Vector3::new(1.0, 1.0, 1.0));
+        assert!((aabb.distance(&Vector3::new(0.0, 0.0, 0.5)) + 0.5).abs() < 1e-12);
+        assert!(close(&aabb.gradient(&Vector3::new(2.0, 0.0, 0.0)), &x));
+        let capsule: Capsule = Capsule::new(&origin, &Vector3::new(0.0, 0.0, 4.0), 1.0);
+        assert!((capsule.distance(&Vector3::new(3.0, 0.0, 2.0)) - 2.0).abs() < 1e-12);
+        let plane: Plane = Plane::new(&origin, &Vector3::new(0.0, 0.0, 1.0));
+        assert!((plane.distance(&Vector3::new(5.0, 5.0, -2.0)) + 2.0).abs() < 1e-12);
+        let torus: Torus = Torus::new(&origin, &Vector3::new(0.0, 0.0, 2.0), 3.0, 1.0);
+        assert!((torus.distance(&origin) - 2.0).abs() < 1e-12);
+        assert!((torus.distance(&Vector3::new(0.0, 3.0, 0.0)) + 1.0).abs() < 1e-12);
+        assert!((torus.distance(&Vector3::new(3.0, 0.0, 3.0)) - 2.0).abs() < 1e-12);
+        let bounds: Aabb = torus.bounding_box();
+        assert!(close(&bounds.min, &Vector3::new(-4.0, -4.0, -1.0)) && close(&bounds.max, &Vector3::new(4.0, 4.0, 1.0)));
+
+        // Sharp and smooth operations
+        let s1: Sphere = Sphere::new(&Vector3::new(-1.0, 0.0, 0.0), 1.0);
+        let s2: Sphere = Sphere::new(&Vector3::new(1.5, 0.0, 0.0), 1.0);
+        let between: Vector3 = Vector3::new(0.25, 0.0, 0.0);
+        let union: Csg<Sphere, Sphere> = Csg::union(s1, s2);
+        assert!((union.distance(&between) - 0.25).abs() < 1e-12);
+        assert!((Csg::difference(s1, s2).distance(&Vector3::new(-1.0, 0.0, 0.0)) + 1.0).abs() < 1e-12);
+        let smooth: SmoothCsg<Sphere, Sphere> = SmoothCsg::union(s1, s2, 1.5);
+        assert!(smooth.distance(&between) < 0.0);
+        assert!((smooth.distance(&Vector3::new(-5.0, 0.0, 0.0)) - 3.0).abs() < 1e-12);
+        assert!((SmoothCsg::union(s1, s2, 0.0).distance(&between) - 0.25).abs() < 1e-12);
+        assert!(SmoothCsg::intersection(s1, s2, 1.0).distance(&between) > 0.25);
+        assert!(SmoothCsg::difference(s1, s2, 0.5).distance(&Vector3::new(-1.0, 0.0, 0.0)) < 0.0);
+
+        // Repetition and transforms
+        let grid: Repeat<Sphere> = Repeat::new(sphere, &Vector3::new(4.0, 4.0, 0.0));
+        assert!((grid.distance(&Vector3::new(8.5, -4.0, 0.0)) + 0.5).abs() < 1e-12);
+        assert!((grid.distance(&Vector3::new(2.0, 0.0, 0.0)) - 1.0).abs() < 1e-12);
+        assert!((grid.distance(&Vector3::new(0.0, 0.0, 8.0)) - 7.0).abs() < 1e-12);
+        let big: Transformed<Sphere> = Transformed::new(sphere, &Transform::scale(&Vector3::new(2.0, 2.0, 2.0)));
+        assert!((big.distance(&Vector3::new(5.0, 0.0, 0.0)) - 3.0).abs() < 1e-12);
+        let ellipsoid: Transformed<Sphere> = Transformed::new(sphere, &Transform::scale(&Vector3::new(3.0, 1.0, 1.0)));
+        let bound: f64 = ellipsoid.distance(&Vector3::new(5.0, 0.0, 0.0));
+        assert!(bound > 0.0 && bound <= 2.0);
+
+        // Sphere tracing gives the same hits as the analytic intersections
+        let line: Line3 = Line3::new(&Vector3::new(-5.0, 0.3, 0.2), &Vector3::new(-4.0, 0.3, 0.2));
+        let traced: SphereTraced<Sphere> = SphereTraced::new(sphere);
+        let expected: Intersection = sphere.closest_intersection(&line).unwrap();
+        let intersection: Intersection = traced.closest_intersection(&line).unwrap();
+        assert!((intersection.lambda - expected.lambda).abs() < 1e-6);
+        let point: Vector3 = line.calc_point(intersection.lambda);
+        assert!(close(&traced.normal(&point), &sphere.normal(&point)));
+        assert_eq!(traced.intersects(&line).iter().count(), 1);
+        assert!(traced.closest_intersection(&Line3::new(&Vector3::new(-5.0, 2.0, 0.0), &Vector3::new(-4.0, 2.0, 0.0))).is_none());
+        let from_inside: Line3 = Line3::new(&origin, &Vector3::new(2.0, 0.0, 0.0));
+        assert!((traced.closest_intersection(&from_inside).unwrap().lambda - 0.5).abs() < 1e-6);
+
+        // Procedural shapes: a torus (missed through its hole) and a row of spheres
+        let traced: SphereTraced<Torus> = SphereTraced::new(torus);
+        assert!(traced.closest_intersection(&Line3::new(&Vector3::new(0.0, 0.0, 10.0), &origin)).is_none());
+        let down: Line3 = Line3::new(&Vector3::new(3.0, 0.0, 10.0), &Vector3::new(3.0, 0.0, 9.0));
+        assert!((traced.closest_intersection(&down).unwrap().lambda - 9.0).abs() < 1e-6);
+        assert!(close(&traced.normal(&Vector3::new(3.0, 0.0, 1.0)), &Vector3::new(0.0, 0.0, 1.0)));
+        let row: SphereTraced<Repeat<Sphere>> = SphereTraced::new(Repeat::new(sphere, &Vector3::new(4.0, 0.0, 0.0)));
+        let along: Line3 = Line3::new(&Vector3::new(2.0, 0.0, 0.0), &Vector3::new(3.0, 0.0, 0.0));
+        assert!((row.closest_intersection(&along).unwrap().lambda - 1.0).abs() < 1e-6);
+        let smooth: SphereTraced<SmoothCsg<Sphere, Sphere>> = SphereTraced::new(SmoothCsg::union(s1, s2, 1.5));
+        let up: Line3 = Line3::new(&Vector3::new(0.25, -5.0, 0.0), &Vector3::new(0.25, -4.0, 0.0));
+        assert!(smooth.closest_intersection(&up).is_some());
+        assert!(union.closest_intersection(&up).is_none());
+    }
 }
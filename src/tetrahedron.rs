@@ -0,0 +1,229 @@
+use vector3::Vector3;
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, ClosestPoint, Support, Bounded, farthest_point};
+use crate::triangle3::closest_barycentric;
+use crate::line3::Line3;
+use list::List;
+
+// Struct that define a tetrahedron in 3D space by its four vertexes.
+// Face `i` is the face opposite to vertex `i` (a = 0, b = 1, c = 2, d = 3), so the barycentric
+// coordinate `i` of a point is zero when the point lies on face `i`.
+#[derive(Clone, Copy)]
+pub struct Tetrahedron {
+    pub a: Vector3, // First vertex of the tetrahedron
+    pub b: Vector3, // Second vertex
+    pub c: Vector3, // Third vertex
+    pub d: Vector3, // Fourth vertex
+
+    ga: Vector3, // Gradients of each barycentric coordinate, precalculated to speed up barycentric calculus
+    gb: Vector3,
+    gc: Vector3,
+    gd: Vector3,
+    volume: f64,
+}
+
+impl Tetrahedron {
+    /// Creates a new `Tetrahedron` from four points.
+    /// # Arguments
+    /// * `a` - A reference to the first vertex of the tetrahedron.
+    /// * `b` - A reference to the second vertex of the tetrahedron.
+    /// * `c` - A reference to the third vertex of the tetrahedron.
+    /// * `d` - A reference to the fourth vertex of the tetrahedron.
+    ///
+    /// The vertexes can be given in any order: the orientation is handled internally.
+    /// # Returns
+    /// A new `Tetrahedron` with the given vertices.
+    pub fn new(a: &Vector3, b: &Vector3, c: &Vector3, d: &Vector3) -> Tetrahedron {
+        let ab: Vector3 = *b - *a;
+        let ac: Vector3 = *c - *a;
+        let ad: Vector3 = *d - *a;
+
+        // Six times the signed volume (scalar triple product)
+        let vol6: f64 = ab.dot(&ac.cross(&ad));
+        if vol6 == 0. {
+            panic!("The tetrahedron cannot be defined by four coplanar points.");
+        }
+
+        // bar_b = ap · (ac x ad) / vol6, and so on: the gradients are the rows of the inverse matrix
+        let gb: Vector3 = ac.cross(&ad) / vol6;
+        let gc: Vector3 = ad.cross(&ab) / vol6;
+        let gd: Vector3 = ab.cross(&ac) / vol6;
+        let ga: Vector3 = (gb + gc + gd) * -1.0;
+
+        Self { a: *a, b: *b, c: *c, d: *d, ga, gb, gc, gd, volume: vol6.abs() / 6.0 }
+    }
+
+    /// Returns the barycentric coordinates of a point relative to the tetrahedron.
+    /// # Arguments
+    /// * `p` - A reference to the point to calculate the barycentric coordinates.
+    /// # Returns
+    /// An array with the barycentric coordinates of the point, in the order of the vertices (a, b, c, d).
+    /// The sum of the coordinates is always 1.
+    /// If the point is inside the tetrahedron, all the coordinates are between 0 and 1.
+    /// The point (or any other value associated to the vertexes) is calculated as
+    /// `p = a * bar[0] + b * bar[1] + c * bar[2] + d * bar[3]`.
+    pub fn barycentric(&self, p: &Vector3) -> [f64; 4] {
+        let ap: Vector3 = *p - self.a;
+        let bar_b: f64 = ap.dot(&self.gb);
+        let bar_c: f64 = ap.dot(&self.gc);
+        let bar_d: f64 = ap.dot(&self.gd);
+        let bar_a: f64 = 1.0 - bar_b - bar_c - bar_d;
+
+        [bar_a, bar_b, bar_c, bar_d]
+    }
+
+    /// Returns true if the point is inside the tetrahedron (or in its surface).
+    pub fn contains(&self, p: &Vector3) -> bool {
+        self.barycentric(p).iter().all(|&bar| bar >= 0.0)
+    }
+
+    /// Returns the volume of the tetrahedron.
+    pub fn volume(&self) -> f64 {
+        self.volume
+    }
+
+    /// Returns the outward normal (normalized) of the face `face` (the face opposite to the vertex with that index).
+    pub fn face_normal(&self, face: usize) -> Vector3 {
+        (self.gradients()[face] * -1.0).normalize()
+    }
+
+    /// Returns the entry and exit points of a line through the tetrahedron.
+    /// If the line doesn´t cross the tetrahedron, it returns Option None.
+    /// Otherwise, it returns `[(entry_lambda, entry_face), (exit_lambda, exit_face)]`, where the faces are
+    /// identified by the index of their opposite vertex.
+    /// If the line just touches an edge or a vertex, entry and exit lambdas are equal.
+    pub fn entry_exit(&self, line: &Line3) -> Option<[(f64, usize); 2]> {
+        let bar: [f64; 4] = self.barycentric(&line.a);
+        let gradients: [Vector3; 4] = self.gradients();
+
+        let mut entry: (f64, usize) = (f64::NEG_INFINITY, 0);
+        let mut exit: (f64, usize) = (f64::INFINITY, 0);
+        for face in 0..4 {
+            // Barycentric coordinate along the line: bar(lambda) = bar[face] + lambda * rate
+            let rate: f64 = gradients[face].dot(&line.v);
+            if rate == 0. {
+                if bar[face] < 0. {
+                    return None;
+                }
+                continue;
+            }
+            let lambda: f64 = -bar[face] / rate;
+            if rate > 0. {
+                if lambda > entry.0 {
+                    entry = (lambda, face);
+                }
+            } else if lambda < exit.0 {
+                exit = (lambda, face);
+            }
+        }
+
+        if entry.0 > exit.0 {
+            return None;
+        }
+        Some([entry, exit])
+    }
+
+    /// Returns the signed distance between a point and the surface of the tetrahedron.
+    /// It is positive outside the tetrahedron, and negative inside.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        let dist: f64 = self.dist_point(p);
+        if self.contains(p) { -dist } else { dist }
+    }
+
+    /// Returns the vertexes of the face `face` (the face opposite to the vertex with that index).
+    pub fn face(&self, face: usize) -> [Vector3; 3] {
+        match face {
+            0 => [self.b, self.c, self.d],
+            1 => [self.a, self.c, self.d],
+            2 => [self.a, self.b, self.d],
+            _ => [self.a, self.b, self.c],
+        }
+    }
+
+    fn gradients(&self) -> [Vector3; 4] {
+        [self.ga, self.gb, self.gc, self.gd]
+    }
+}
+
+impl Support for Tetrahedron {
+    /// Returns the vertex of the tetrahedron farthest in a given direction.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        farthest_point(&[self.a, self.b, self.c, self.d], dir)
+    }
+}
+
+impl Bounded for Tetrahedron {
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&[self.a, self.b, self.c, self.d])
+    }
+}
+
+impl Shape for Tetrahedron {
+    /// Returns the outward normal (normalized) of the tetrahedron at a given point.
+    /// The face used is the one closest to the point (the point should be in the surface of the tetrahedron).
+    fn normal(&self, point: &Vector3) -> Vector3 {
+        let bar: [f64; 4] = self.barycentric(point);
+        let gradients: [Vector3; 4] = self.gradients();
+
+        // The distance to face i is bar[i] / |gradient i|
+        let mut face: usize = 0;
+        let mut min_dist: f64 = f64::INFINITY;
+        for i in 0..4 {
+            let dist: f64 = (bar[i] / gradients[i].magnitude()).abs();
+            if dist < min_dist {
+                min_dist = dist;
+                face = i;
+            }
+        }
+        self.face_normal(face)
+    }
+
+    /// Returns the intersections of the tetrahedron with a line.
+    /// If the line doesn´t cross the tetrahedron, it returns an empty list.
+    /// If the line crosses the tetrahedron, it returns a list with the entry and exit lambdas (in that order).
+    /// If the line just touches an edge or a vertex, it returns a list with the single lambda value.
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        let mut intersections: List<f64> = List::<f64>::new();
+
+        if let Some([entry, exit]) = self.entry_exit(line) {
+            if exit.0 != entry.0 {
+                intersections.push(exit.0);
+            }
+            intersections.push(entry.0);
+        }
+        intersections
+    }
+
+    /// Returns the closest positive intersection of the tetrahedron with a line.
+    /// If the line doesn´t collide the tetrahedron, it returns Option None.
+    /// If the line starts inside the tetrahedron, the exit lambda is returned.
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        let [entry, exit] = self.entry_exit(line)?;
+        if entry.0 > 0. {
+            Some(Intersection::new(entry.0, None))
+        } else if exit.0 > 0. {
+            Some(Intersection::new(exit.0, None))
+        } else {
+            None
+        }
+    }
+}
+
+impl ClosestPoint for Tetrahedron {
+    /// Returns the point of the surface of the tetrahedron closest to a given point.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let mut closest: Vector3 = self.a;
+        let mut min_dist: f64 = f64::INFINITY;
+        for face in 0..4 {
+            let [a, b, c] = self.face(face);
+            let bar: Vector3 = closest_barycentric(&a, &b, &c, p);
+            let q: Vector3 = a * bar.x + b * bar.y + c * bar.z;
+            let dist: f64 = q.distance_to(*p);
+            if dist < min_dist {
+                min_dist = dist;
+                closest = q;
+            }
+        }
+        closest
+    }
+}
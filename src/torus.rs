@@ -0,0 +1,57 @@
+use crate::aabb::Aabb;
+use crate::base::Bounded;
+use vector3::Vector3;
+
+use std::fmt;
+
+/// A torus in 3D space: all the points within a distance `minor` of a circle of radius `major` (its spine).
+#[derive(Clone, Copy)]
+pub struct Torus {
+    pub c: Vector3, // Center of the torus
+    pub n: Vector3, // Axis of the torus, normal of the plane of the spine (already normalized)
+    pub major: f64, // Radius of the spine circle
+    pub minor: f64, // Radius of the tube
+}
+
+impl Torus {
+    /// Creates a new `Torus`.
+    /// The torus is defined by its center, `c`, its axis, `n`, the radius of the spine circle, `major`, and the
+    /// radius of the tube around it, `minor`.
+    pub fn new(c: &Vector3, n: &Vector3, major: f64, minor: f64) -> Torus {
+        if n.is_zero() {
+            panic!("The axis of the torus cannot be zero");
+        }
+        if major < 0. || minor < 0. {
+            panic!("The radii of the torus cannot be negative");
+        }
+        Self { c: *c, n: n.normalize(), major, minor }
+    }
+
+    /// Returns the signed distance between a point and the surface of the torus.
+    /// It is positive outside the torus, and negative inside.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        let cp: Vector3 = *p - self.c;
+        let height: f64 = cp.dot(&self.n);
+        let radial: f64 = (cp - self.n * height).magnitude();
+        (radial - self.major).hypot(height) - self.minor
+    }
+}
+
+impl Bounded for Torus {
+    fn bounding_box(&self) -> Aabb {
+        // The spine circle spans major * sin(angle between the axis and each world axis)
+        let extent = |n: f64| self.major * (1.0 - n * n).max(0.0).sqrt() + self.minor;
+        let half: Vector3 = Vector3::new(extent(self.n.x), extent(self.n.y), extent(self.n.z));
+        Aabb::new(&(self.c - half), &(self.c + half))
+    }
+}
+
+impl fmt::Display for Torus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "C ({}, {}, {}) N ({}, {}, {}) Major radius = {} Minor radius = {}",
+            self.c.x, self.c.y, self.c.z, self.n.x, self.n.y, self.n.z, self.major, self.minor
+        )
+    }
+}
@@ -0,0 +1,346 @@
+use crate::aabb::Aabb;
+use crate::capsule::Capsule;
+use crate::circle3::Circle3;
+use crate::convex_hull::ConvexHull;
+use crate::line3::Line3;
+use crate::obb::Obb;
+use crate::plane::Plane;
+use crate::polygon3::Polygon3;
+use crate::quaternion::Quaternion;
+use crate::sphere::Sphere;
+use crate::tetrahedron::Tetrahedron;
+use crate::triangle3::Triangle3;
+use vector3::Vector3;
+
+use std::fmt;
+
+// Tolerance for the determinant of the linear part of a transform, to consider it singular
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+/// Affine transform in 3D space, stored as a 4x4 matrix (row by row, applied to column vectors) with its inverse.
+/// The last row of the matrix is always `[0, 0, 0, 1]`.
+#[derive(Clone, Copy)]
+pub struct Transform {
+    m: [[f64; 4]; 4],   // Matrix of the transform
+    inv: [[f64; 4]; 4], // Inverse matrix, precalculated to transform normals and to undo the transform
+}
+
+impl Transform {
+    /// Creates the identity `Transform`.
+    pub fn identity() -> Transform {
+        Self { m: IDENTITY, inv: IDENTITY }
+    }
+
+    /// Creates a new `Transform` from a 4x4 matrix (row by row, applied to column vectors).
+    /// The matrix must be affine (last row `[0, 0, 0, 1]`) and invertible.
+    pub fn from_matrix(m: &[[f64; 4]; 4]) -> Transform {
+        if m[3] != [0.0, 0.0, 0.0, 1.0] {
+            panic!("The matrix of the transform must be affine (last row [0, 0, 0, 1])");
+        }
+        Self { m: *m, inv: affine_inverse(m) }
+    }
+
+    /// Creates a translation `Transform`.
+    pub fn translation(t: &Vector3) -> Transform {
+        let m: [[f64; 4]; 4] = [
+            [1.0, 0.0, 0.0, t.x],
+            [0.0, 1.0, 0.0, t.y],
+            [0.0, 0.0, 1.0, t.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let inv: [[f64; 4]; 4] = [
+            [1.0, 0.0, 0.0, -t.x],
+            [0.0, 1.0, 0.0, -t.y],
+            [0.0, 0.0, 1.0, -t.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        Self { m, inv }
+    }
+
+    /// Creates a (non-uniform) scale `Transform`, with the scale factor of each axis.
+    pub fn scale(s: &Vector3) -> Transform {
+        if s.x == 0. || s.y == 0. || s.z == 0. {
+            panic!("The scale factors of the transform cannot be zero");
+        }
+        let m: [[f64; 4]; 4] = [
+            [s.x, 0.0, 0.0, 0.0],
+            [0.0, s.y, 0.0, 0.0],
+            [0.0, 0.0, s.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let inv: [[f64; 4]; 4] = [
+            [1.0 / s.x, 0.0, 0.0, 0.0],
+            [0.0, 1.0 / s.y, 0.0, 0.0],
+            [0.0, 0.0, 1.0 / s.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        Self { m, inv }
+    }
+
+    /// Creates a rotation `Transform`, given the axis of rotation and the angle (in radians, anticlockwise around
+    /// the axis).
+    pub fn rotation(axis: &Vector3, angle: f64) -> Transform {
+        Transform::from(Quaternion::from_axis_angle(axis, angle))
+    }
+
+    /// Returns the matrix of the transform.
+    pub fn matrix(&self) -> [[f64; 4]; 4] {
+        self.m
+    }
+
+    /// Returns the inverse transform.
+    pub fn inverse(&self) -> Transform {
+        Self { m: self.inv, inv: self.m }
+    }
+
+    /// Returns the transform that applies `self` first, and then `next`.
+    pub fn then(&self, next: &Transform) -> Transform {
+        Self { m: multiply(&next.m, &self.m), inv: multiply(&self.inv, &next.inv) }
+    }
+
+    /// Returns the normal matrix: the inverse transpose of the linear part of the transform, used to transform
+    /// normals so they stay perpendicular to the transformed surfaces.
+    pub fn normal_matrix(&self) -> [[f64; 3]; 3] {
+        let mut n: [[f64; 3]; 3] = [[0.0; 3]; 3];
+        for (i, row) in n.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = self.inv[j][i];
+            }
+        }
+        n
+    }
+
+    /// Transforms a point (affected by the translation).
+    pub fn transform_point(&self, p: &Vector3) -> Vector3 {
+        apply(&self.m, p, 1.0)
+    }
+
+    /// Transforms a direction (not affected by the translation, and not normalized).
+    pub fn transform_vector(&self, v: &Vector3) -> Vector3 {
+        apply(&self.m, v, 0.0)
+    }
+
+    /// Transforms the normal of a surface with the normal matrix (the result is normalized).
+    pub fn transform_normal(&self, n: &Vector3) -> Vector3 {
+        // Transposed inverse: the columns of the inverse are the rows of the normal matrix
+        Vector3::new(
+            self.inv[0][0] * n.x + self.inv[1][0] * n.y + self.inv[2][0] * n.z,
+            self.inv[0][1] * n.x + self.inv[1][1] * n.y + self.inv[2][1] * n.z,
+            self.inv[0][2] * n.x + self.inv[1][2] * n.y + self.inv[2][2] * n.z,
+        ).normalize()
+    }
+
+    /// Returns the largest scale factor of the transform: the maximum length of a transformed unit vector.
+    /// This is the factor applied to the radius of spheres (and capsules), so the result contains the real
+    /// (ellipsoidal) shape.
+    pub fn max_scale(&self) -> f64 {
+        // Square root of the largest eigenvalue of A^T A, with A the linear part
+        let mut ata: [[f64; 3]; 3] = [[0.0; 3]; 3];
+        for (i, row) in ata.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.m[k][i] * self.m[k][j]).sum();
+            }
+        }
+        largest_symmetric_eigenvalue(&ata).sqrt()
+    }
+}
+
+/// Shapes (and lines) that can be moved with an affine transform.
+/// Cached data (like `Line3::qa` or `Plane::d`) is recalculated for the transformed shape.
+pub trait Transformable {
+    /// Returns the shape transformed.
+    fn transform(&self, t: &Transform) -> Self;
+
+    /// Returns the shape rotated around the origin.
+    fn rotate(&self, q: &Quaternion) -> Self where Self: Sized {
+        self.transform(&Transform::from(*q))
+    }
+}
+
+impl Transformable for Line3 {
+    /// The lambdas are preserved: `t(line.calc_point(lambda)) = line.transform(t).calc_point(lambda)`.
+    fn transform(&self, t: &Transform) -> Line3 {
+        Line3::new(&t.transform_point(&self.a), &t.transform_point(&(self.a + self.v)))
+    }
+}
+
+impl Transformable for Plane {
+    fn transform(&self, t: &Transform) -> Plane {
+        Plane::new(&t.transform_point(&self.a), &t.transform_normal(&self.n))
+    }
+}
+
+impl Transformable for Sphere {
+    /// A non-uniform scale would turn the sphere into an ellipsoid: the radius is scaled with the largest scale
+    /// factor of the transform, so the result is the smallest sphere (with the transformed center) that contains it.
+    fn transform(&self, t: &Transform) -> Sphere {
+        Sphere::new(&t.transform_point(&self.c), self.r * t.max_scale())
+    }
+}
+
+impl Transformable for Triangle3 {
+    /// The order of the vertexes is kept, so a mirroring transform flips the visible side of the triangle.
+    fn transform(&self, t: &Transform) -> Triangle3 {
+        Triangle3::new(&t.transform_point(&self.a), &t.transform_point(&self.b), &t.transform_point(&self.c))
+    }
+}
+
+impl Transformable for Tetrahedron {
+    fn transform(&self, t: &Transform) -> Tetrahedron {
+        Tetrahedron::new(
+            &t.transform_point(&self.a),
+            &t.transform_point(&self.b),
+            &t.transform_point(&self.c),
+            &t.transform_point(&self.d),
+        )
+    }
+}
+
+impl Transformable for Polygon3 {
+    /// The order of the vertexes is kept, so a mirroring transform flips the visible side of the polygon.
+    fn transform(&self, t: &Transform) -> Polygon3 {
+        let vertices: Vec<Vector3> = self.vertices.iter().map(|p| t.transform_point(p)).collect();
+        Polygon3::new(&vertices)
+    }
+}
+
+impl Transformable for Aabb {
+    /// A rotated box is not axis aligned anymore: the result is the smallest `Aabb` that contains it.
+    fn transform(&self, t: &Transform) -> Aabb {
+        let vertices: Vec<Vector3> = self.vertices().iter().map(|p| t.transform_point(p)).collect();
+        Aabb::from_points(&vertices)
+    }
+}
+
+impl Transformable for Capsule {
+    /// Like spheres, the radius is scaled with the largest scale factor of the transform.
+    fn transform(&self, t: &Transform) -> Capsule {
+        Capsule::new(&t.transform_point(&self.a), &t.transform_point(&self.b), self.r * t.max_scale())
+    }
+}
+
+impl Transformable for Obb {
+    /// A non-uniform scale (or a shear) would turn the box into a parallelepiped: the first axis of the result is the
+    /// transformed first axis, and the size of the box is the one needed to contain the parallelepiped. Rotations,
+    /// translations and uniform scales are exact.
+    fn transform(&self, t: &Transform) -> Obb {
+        let h: [f64; 3] = [self.half_extents.x, self.half_extents.y, self.half_extents.z];
+        let edges: Vec<Vector3> = (0..3).map(|i| t.transform_vector(&(self.axes[i] * h[i]))).collect();
+
+        // Right handed orthonormal axes (the linear part is invertible, so the transformed axes aren´t aligned)
+        let x: Vector3 = t.transform_vector(&self.axes[0]).normalize();
+        let y_image: Vector3 = t.transform_vector(&self.axes[1]);
+        let y: Vector3 = (y_image - x * y_image.dot(&x)).normalize();
+        let z: Vector3 = x.cross(&y);
+        let extent = |axis: &Vector3| -> f64 { edges.iter().map(|e| e.dot(axis).abs()).sum() };
+        Obb::new(&t.transform_point(&self.c), &x, &y, &Vector3::new(extent(&x), extent(&y), extent(&z)))
+    }
+}
+
+impl Transformable for Circle3 {
+    /// A non-uniform scale would turn the circle into an ellipse: like spheres, the radius is scaled with the largest
+    /// scale factor of the transform, so the result (in the transformed plane) contains it.
+    fn transform(&self, t: &Transform) -> Circle3 {
+        Circle3::new(&t.transform_point(&self.c), &t.transform_normal(&self.n), self.r * t.max_scale())
+    }
+}
+
+impl Transformable for ConvexHull {
+    fn transform(&self, t: &Transform) -> ConvexHull {
+        let points: Vec<Vector3> = self.points.iter().map(|p| t.transform_point(p)).collect();
+        ConvexHull::new(&points)
+    }
+}
+
+impl From<Quaternion> for Transform {
+    /// Creates the rotation `Transform` of a quaternion (that doesn´t need to be normalized).
+    fn from(q: Quaternion) -> Transform {
+        let r: [[f64; 3]; 3] = q.to_matrix();
+        // The inverse of a rotation is its transpose
+        let mut m: [[f64; 4]; 4] = IDENTITY;
+        let mut inv: [[f64; 4]; 4] = IDENTITY;
+        for i in 0..3 {
+            for j in 0..3 {
+                m[i][j] = r[i][j];
+                inv[j][i] = r[i][j];
+            }
+        }
+        Self { m, inv }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.m.iter() {
+            writeln!(f, "[{}, {}, {}, {}]", row[0], row[1], row[2], row[3])?;
+        }
+        Ok(())
+    }
+}
+
+const IDENTITY: [[f64; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+fn apply(m: &[[f64; 4]; 4], p: &Vector3, w: f64) -> Vector3 {
+    Vector3::new(
+        m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3] * w,
+        m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3] * w,
+        m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3] * w,
+    )
+}
+
+fn multiply(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    let mut result: [[f64; 4]; 4] = [[0.0; 4]; 4];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    result
+}
+
+// Inverse of an affine matrix: the inverse of the linear part (by cofactors), and the translation undone
+fn affine_inverse(m: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    let cofactor = |r1: usize, r2: usize, c1: usize, c2: usize| m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1];
+    // Transposed cofactors (adjugate)
+    let adjugate: [[f64; 3]; 3] = [
+        [cofactor(1, 2, 1, 2), -cofactor(0, 2, 1, 2), cofactor(0, 1, 1, 2)],
+        [-cofactor(1, 2, 0, 2), cofactor(0, 2, 0, 2), -cofactor(0, 1, 0, 2)],
+        [cofactor(1, 2, 0, 1), -cofactor(0, 2, 0, 1), cofactor(0, 1, 0, 1)],
+    ];
+    let det: f64 = m[0][0] * adjugate[0][0] + m[0][1] * adjugate[1][0] + m[0][2] * adjugate[2][0];
+    let size: f64 = (0..3).flat_map(|i| (0..3).map(move |j| (i, j))).map(|(i, j)| m[i][j].abs()).fold(0.0, f64::max);
+    if det.abs() <= SINGULAR_TOLERANCE * size.powi(3) {
+        panic!("The matrix of the transform must be invertible");
+    }
+
+    let mut inv: [[f64; 4]; 4] = IDENTITY;
+    for i in 0..3 {
+        for j in 0..3 {
+            inv[i][j] = adjugate[i][j] / det;
+        }
+        inv[i][3] = -(0..3).map(|k| inv[i][k] * m[k][3]).sum::<f64>();
+    }
+    inv
+}
+
+// Largest eigenvalue of a symmetric 3x3 matrix (trigonometric solution of the characteristic cubic)
+fn largest_symmetric_eigenvalue(a: &[[f64; 3]; 3]) -> f64 {
+    let off: f64 = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+    if off == 0. {
+        return a[0][0].max(a[1][1]).max(a[2][2]);
+    }
+    let q: f64 = (a[0][0] + a[1][1] + a[2][2]) / 3.0;
+    let p: f64 = (((a[0][0] - q).powi(2) + (a[1][1] - q).powi(2) + (a[2][2] - q).powi(2) + 2.0 * off) / 6.0).sqrt();
+    // B = (A - q I) / p, and r = det(B) / 2
+    let b = |i: usize, j: usize| -> f64 { (a[i][j] - if i == j { q } else { 0.0 }) / p };
+    let det: f64 = b(0, 0) * (b(1, 1) * b(2, 2) - b(1, 2) * b(2, 1))
+        - b(0, 1) * (b(1, 0) * b(2, 2) - b(1, 2) * b(2, 0))
+        + b(0, 2) * (b(1, 0) * b(2, 1) - b(1, 1) * b(2, 0));
+    let phi: f64 = (det / 2.0).clamp(-1.0, 1.0).acos() / 3.0;
+    q + 2.0 * p * phi.cos()
+}
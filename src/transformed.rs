@@ -0,0 +1,85 @@
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, ClosestPoint, Support, Bounded};
+use crate::line3::Line3;
+use crate::transform::{Transform, Transformable};
+use list::List;
+use vector3::Vector3;
+
+/// A shape placed in the world with a transform (an instance), without copying or rebuilding it.
+/// The queries move the line to the space of the shape with the inverse transform. As the director vector of the
+/// line is transformed too (not normalized), the lambdas are valid in the world space.
+#[derive(Clone)]
+pub struct Transformed<S> {
+    pub shape: S,             // Shape, in its own (object) space
+    pub transform: Transform, // Transform from the object space to the world space
+}
+
+impl<S> Transformed<S> {
+    /// Creates a new `Transformed` shape, given the shape (in object space) and its object to world transform.
+    pub fn new(shape: S, transform: &Transform) -> Transformed<S> {
+        Self { shape, transform: *transform }
+    }
+
+    /// Moves a line from the world space to the object space of the shape (keeping its lambdas).
+    pub fn to_object(&self, line: &Line3) -> Line3 {
+        line.transform(&self.transform.inverse())
+    }
+}
+
+impl<S: Shape> Shape for Transformed<S> {
+    /// Returns the normal (normalized) of the shape at a given point (in world space).
+    /// The normal of the inner shape is transformed with the normal matrix, so it stays perpendicular to the surface
+    /// even with non-uniform scales.
+    fn normal(&self, point: &Vector3) -> Vector3 {
+        let local: Vector3 = self.transform.inverse().transform_point(point);
+        self.transform.transform_normal(&self.shape.normal(&local))
+    }
+
+    /// Returns the intersections of the shape with a line (in world space).
+    fn intersects(&self, line: &Line3) -> List<f64> {
+        self.shape.intersects(&self.to_object(line))
+    }
+
+    /// Returns the closest positive intersection of the shape with a line (in world space).
+    /// The barycentric coordinates (if any) don´t change with the transform.
+    fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
+        self.shape.closest_intersection(&self.to_object(line))
+    }
+}
+
+impl<S: ClosestPoint> ClosestPoint for Transformed<S> {
+    /// Returns the point of the transformed shape closest to a given point.
+    /// It is exact for rigid transforms and uniform scales. With non-uniform scales the closest point is searched in
+    /// the object space, so it is a point of the surface, but maybe not the closest one.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let local: Vector3 = self.transform.inverse().transform_point(p);
+        self.transform.transform_point(&self.shape.closest_point(&local))
+    }
+}
+
+impl<S: Support> Support for Transformed<S> {
+    /// Returns the point of the transformed shape farthest in a given direction.
+    /// For an affine transform `M x + t`, it is the transformed support of the shape in the direction `M^T dir`.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        let m: [[f64; 4]; 4] = self.transform.matrix();
+        let local: Vector3 = Vector3::new(
+            m[0][0] * dir.x + m[1][0] * dir.y + m[2][0] * dir.z,
+            m[0][1] * dir.x + m[1][1] * dir.y + m[2][1] * dir.z,
+            m[0][2] * dir.x + m[1][2] * dir.y + m[2][2] * dir.z,
+        );
+        self.transform.transform_point(&self.shape.support(&local))
+    }
+}
+
+impl<S: Bounded> Bounded for Transformed<S> {
+    fn bounding_box(&self) -> Aabb {
+        self.shape.bounding_box().transform(&self.transform)
+    }
+}
+
+impl<S: Clone> Transformable for Transformed<S> {
+    /// The transforms are composed: the inner shape is not modified.
+    fn transform(&self, t: &Transform) -> Transformed<S> {
+        Self { shape: self.shape.clone(), transform: self.transform.then(t) }
+    }
+}
@@ -1,9 +1,22 @@
 use vector3::Vector3;
-use crate::base::{Shape, Intersection};
+use crate::aabb::Aabb;
+use crate::base::{Shape, Intersection, ClosestPoint, Support, Bounded, farthest_point};
 use crate::plane::Plane;
 use crate::line3::Line3;
 use list::List;
 
+// Tolerance (relative to the size of the triangles) to consider a point in a plane, or two points equal
+const TOLERANCE: f64 = 1e-10;
+
+/// Result of the intersection of two triangles.
+#[derive(Clone, Debug)]
+pub enum TriangleIntersection {
+    None,                        // The triangles don´t intersect
+    Point(Vector3),              // The triangles just touch in a point
+    Segment(Vector3, Vector3),   // The triangles intersect in a segment
+    Coplanar(Vec<Vector3>),      // The triangles are coplanar, and overlap in this convex polygon
+}
+
 // Struct that define a triangle in 3D space, with the vertex defined in an
 // anticlockwise order.
 #[derive(Clone, Copy)]
@@ -28,6 +41,7 @@ impl Triangle3 {
     /// * `a` - A reference to the first vertex of the triangle.
     /// * `b` - A reference to the second vertex of the triangle.
     /// * `c` - A reference to the third vertex of the triangle.
+    ///
     /// a, b, c must be defined in anticlockwise order (looked from the visible side)
     /// # Returns
     /// A new `Triangle3` with the given vertices.
@@ -36,7 +50,7 @@ impl Triangle3 {
         let ac: Vector3 = *c - *a;
 
         let n: Vector3 = ab.cross(&ac).normalize();
-        let plane: Plane = Plane::new(&a, &n);
+        let plane: Plane = Plane::new(a, &n);
 
         let d00: f64 = ab.dot(&ab);
         let d01: f64 = ab.dot(&ac);
@@ -71,6 +85,238 @@ impl Triangle3 {
         Vector3{x: bar_a, y: bar_b, z: bar_c }
     }
 
+    /// Returns the barycentric coordinates of the point of the triangle closest to a given point.
+    /// # Arguments
+    /// * `p` - A reference to the point (that can be anywhere, not only in the plane of the triangle).
+    /// # Returns
+    /// A new `Vector3` with the barycentric coordinates (in the order of the vertices a, b, c) of the closest point.
+    /// All of them are between 0 and 1, and the closest point is `a * bar_a + b * bar_b + c * bar_c`.
+    pub fn closest_barycentric(&self, p: &Vector3) -> Vector3 {
+        closest_barycentric(&self.a, &self.b, &self.c, p)
+    }
+
+    /// Returns the signed distance between a point and the triangle.
+    /// It is positive if the point is in the visible side of the triangle (where the normal points to), and
+    /// negative in the other side.
+    pub fn signed_dist_point(&self, p: &Vector3) -> f64 {
+        let dist: f64 = self.dist_point(p);
+        if self.plane.signed_dist_point(p) < 0. { -dist } else { dist }
+    }
+}
+
+impl Triangle3 {
+    /// Calculates the intersection of two triangles.
+    /// # Returns
+    /// * `TriangleIntersection::None` if they don´t intersect.
+    /// * `TriangleIntersection::Point` if they just touch in a point (like a vertex of one of them in the other).
+    /// * `TriangleIntersection::Segment` if they intersect in a segment (or touch along an edge).
+    /// * `TriangleIntersection::Coplanar` if they are coplanar and their overlap has area, with the vertexes of the
+    ///   overlap polygon (in anticlockwise order relative to `self`). If coplanar triangles just touch, the result
+    ///   is a `Point` or a `Segment` instead.
+    pub fn intersect_triangle(&self, other: &Triangle3) -> TriangleIntersection {
+        let verts1: [Vector3; 3] = [self.a, self.b, self.c];
+        let verts2: [Vector3; 3] = [other.a, other.b, other.c];
+        let size: f64 = verts1.iter().chain(verts2.iter()).map(|p| p.distance_to(self.a)).fold(0.0, f64::max);
+        let eps: f64 = TOLERANCE * size;
+
+        // Signed distances of the vertexes of each triangle to the plane of the other one
+        let dist2: [f64; 3] = verts2.map(|p| snap(self.plane.signed_dist_point(&p), eps));
+        if dist2.iter().all(|&d| d > 0.) || dist2.iter().all(|&d| d < 0.) {
+            return TriangleIntersection::None;
+        }
+        if dist2.iter().all(|&d| d == 0.) {
+            return self.coplanar_intersection(other, eps);
+        }
+        let dist1: [f64; 3] = verts1.map(|p| snap(other.plane.signed_dist_point(&p), eps));
+        if dist1.iter().all(|&d| d > 0.) || dist1.iter().all(|&d| d < 0.) {
+            return TriangleIntersection::None;
+        }
+
+        // Both triangles cross the line where both planes intersect: compare the sections of each one along it
+        let direction: Vector3 = self.plane.n.cross(&other.plane.n);
+        let (Some(section1), Some(section2)) = (plane_section(&verts1, &dist1, &direction), plane_section(&verts2, &dist2, &direction)) else {
+            return TriangleIntersection::None;
+        };
+        let start: (f64, Vector3) = if section1[0].0 > section2[0].0 { section1[0] } else { section2[0] };
+        let end: (f64, Vector3) = if section1[1].0 < section2[1].0 { section1[1] } else { section2[1] };
+
+        let eps_t: f64 = eps * direction.magnitude();
+        if start.0 > end.0 + eps_t {
+            TriangleIntersection::None
+        } else if end.0 - start.0 <= eps_t {
+            TriangleIntersection::Point(start.1)
+        } else {
+            TriangleIntersection::Segment(start.1, end.1)
+        }
+    }
+
+    // Overlap of two coplanar triangles: `self` clipped by each edge of `other` (Sutherland–Hodgman)
+    fn coplanar_intersection(&self, other: &Triangle3, eps: f64) -> TriangleIntersection {
+        let mut polygon: Vec<Vector3> = vec![self.a, self.b, self.c];
+        let edges: [(Vector3, Vector3); 3] = [(other.a, other.b), (other.b, other.c), (other.c, other.a)];
+        for (e0, e1) in edges.iter() {
+            // Distance to the edge, positive towards the inside of `other`
+            let inward: Vector3 = other.plane.n.cross(&(*e1 - *e0)).normalize();
+            let side = |p: &Vector3| snap((*p - *e0).dot(&inward), eps);
+
+            let mut clipped: Vec<Vector3> = Vec::new();
+            for (i, p) in polygon.iter().enumerate() {
+                let q: &Vector3 = &polygon[(i + 1) % polygon.len()];
+                let (sp, sq) = (side(p), side(q));
+                if sp >= 0. {
+                    clipped.push(*p);
+                }
+                if (sp > 0. && sq < 0.) || (sp < 0. && sq > 0.) {
+                    clipped.push(*p + (*q - *p) * (sp / (sp - sq)));
+                }
+            }
+            polygon = clipped;
+            if polygon.is_empty() {
+                return TriangleIntersection::None;
+            }
+        }
+
+        // Remove repeated vertexes (touching triangles produce degenerated polygons)
+        let mut overlap: Vec<Vector3> = Vec::new();
+        for p in polygon {
+            if overlap.iter().all(|q| q.distance_to(p) > eps) {
+                overlap.push(p);
+            }
+        }
+        match overlap.len() {
+            0 => TriangleIntersection::None,
+            1 => TriangleIntersection::Point(overlap[0]),
+            2 => TriangleIntersection::Segment(overlap[0], overlap[1]),
+            _ => {
+                let area2: f64 = (1..overlap.len() - 1)
+                    .map(|i| (overlap[i] - overlap[0]).cross(&(overlap[i + 1] - overlap[0])).magnitude())
+                    .sum();
+                if area2 > eps * size_of(&overlap) {
+                    return TriangleIntersection::Coplanar(overlap);
+                }
+                // Aligned points: the overlap is the segment between the two farthest ones
+                let mut ends: (Vector3, Vector3) = (overlap[0], overlap[1]);
+                for (i, p) in overlap.iter().enumerate() {
+                    for q in overlap[i + 1..].iter() {
+                        if p.distance_to(*q) > ends.0.distance_to(ends.1) {
+                            ends = (*p, *q);
+                        }
+                    }
+                }
+                TriangleIntersection::Segment(ends.0, ends.1)
+            }
+        }
+    }
+}
+
+// Values closer to zero than the tolerance are considered zero
+fn snap(value: f64, eps: f64) -> f64 {
+    if value.abs() <= eps { 0.0 } else { value }
+}
+
+fn size_of(points: &[Vector3]) -> f64 {
+    points.iter().map(|p| p.distance_to(points[0])).fold(0.0, f64::max)
+}
+
+// Section of a triangle with a plane, given the signed distances of its vertexes to the plane.
+// Returns the ends of the section sorted by their projection along `direction` (that is in the plane), with
+// that projection.
+fn plane_section(verts: &[Vector3; 3], dist: &[f64; 3], direction: &Vector3) -> Option<[(f64, Vector3); 2]> {
+    let mut points: Vec<Vector3> = Vec::new();
+    for i in 0..3 {
+        let j: usize = (i + 1) % 3;
+        if dist[i] == 0. {
+            points.push(verts[i]);
+        }
+        if (dist[i] > 0. && dist[j] < 0.) || (dist[i] < 0. && dist[j] > 0.) {
+            points.push(verts[i] + (verts[j] - verts[i]) * (dist[i] / (dist[i] - dist[j])));
+        }
+    }
+
+    let projected = points.iter().map(|p| (p.dot(direction), *p));
+    let min: (f64, Vector3) = projected.clone().min_by(|p, q| p.0.total_cmp(&q.0))?;
+    let max: (f64, Vector3) = projected.max_by(|p, q| p.0.total_cmp(&q.0))?;
+    Some([min, max])
+}
+
+/// Barycentric coordinates of the point of the triangle (a, b, c) closest to p.
+/// Finds the Voronoi region (vertex, edge or face) of the triangle where p lies, so the closest point is the vertex,
+/// the projection on the edge or the projection on the face respectively.
+pub(crate) fn closest_barycentric(a: &Vector3, b: &Vector3, c: &Vector3, p: &Vector3) -> Vector3 {
+    let ab: Vector3 = *b - *a;
+    let ac: Vector3 = *c - *a;
+
+    // Vertex region of a
+    let ap: Vector3 = *p - *a;
+    let d1: f64 = ab.dot(&ap);
+    let d2: f64 = ac.dot(&ap);
+    if d1 <= 0. && d2 <= 0. {
+        return Vector3::new(1.0, 0.0, 0.0);
+    }
+
+    // Vertex region of b
+    let bp: Vector3 = *p - *b;
+    let d3: f64 = ab.dot(&bp);
+    let d4: f64 = ac.dot(&bp);
+    if d3 >= 0. && d4 <= d3 {
+        return Vector3::new(0.0, 1.0, 0.0);
+    }
+
+    // Edge region of ab
+    let vc: f64 = d1 * d4 - d3 * d2;
+    if vc <= 0. && d1 >= 0. && d3 <= 0. {
+        let v: f64 = d1 / (d1 - d3);
+        return Vector3::new(1.0 - v, v, 0.0);
+    }
+
+    // Vertex region of c
+    let cp: Vector3 = *p - *c;
+    let d5: f64 = ab.dot(&cp);
+    let d6: f64 = ac.dot(&cp);
+    if d6 >= 0. && d5 <= d6 {
+        return Vector3::new(0.0, 0.0, 1.0);
+    }
+
+    // Edge region of ac
+    let vb: f64 = d5 * d2 - d1 * d6;
+    if vb <= 0. && d2 >= 0. && d6 <= 0. {
+        let w: f64 = d2 / (d2 - d6);
+        return Vector3::new(1.0 - w, 0.0, w);
+    }
+
+    // Edge region of bc
+    let va: f64 = d3 * d6 - d5 * d4;
+    if va <= 0. && (d4 - d3) >= 0. && (d5 - d6) >= 0. {
+        let w: f64 = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return Vector3::new(0.0, 1.0 - w, w);
+    }
+
+    // Face region
+    let denom: f64 = 1.0 / (va + vb + vc);
+    let v: f64 = vb * denom;
+    let w: f64 = vc * denom;
+    Vector3::new(1.0 - v - w, v, w)
+}
+
+impl ClosestPoint for Triangle3 {
+    /// Returns the point of the triangle closest to a given point.
+    fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let bar: Vector3 = self.closest_barycentric(p);
+        self.a * bar.x + self.b * bar.y + self.c * bar.z
+    }
+}
+
+impl Support for Triangle3 {
+    /// Returns the vertex of the triangle farthest in a given direction.
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        farthest_point(&[self.a, self.b, self.c], dir)
+    }
+}
+
+impl Bounded for Triangle3 {
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&[self.a, self.b, self.c])
+    }
 }
 
 impl Shape for Triangle3 {
@@ -86,7 +332,7 @@ impl Shape for Triangle3 {
     fn intersects(&self, line: &Line3) -> List::<f64> {
         let intersections: List<f64> = self.plane.intersects(line);
         if intersections.iter().count() == 0 {
-            return intersections;
+            intersections
         }
         else {
             let p: Vector3 = line.calc_point(*intersections.iter().next().unwrap());
@@ -94,10 +340,10 @@ impl Shape for Triangle3 {
             if  1.0 >= b.x && b.x >= 0.0 && 
                 1.0 >= b.y && b.y >= 0.0 && 
                 1.0 >= b.z && b.z >= 0.0 {
-                return intersections;
+                intersections
             }
             else {
-                return List::<f64>::new();
+                List::<f64>::new()
             }
         }
     }
@@ -107,21 +353,16 @@ impl Shape for Triangle3 {
     /// If the line is in the plane of the triangle, it returns an empty list as a convention (because really, all lambdas fulfill).
     /// If the line intersects the triangle, it returns a list with the lambda value.
     fn closest_intersection(&self, line: &Line3) -> Option<Intersection> {
-        let intersection: Option<Intersection> = self.plane.closest_intersection(line);
-        if intersection.is_none() {
-            return None;
+        let intersection: Intersection = self.plane.closest_intersection(line)?;
+        let p: Vector3 = line.calc_point(intersection.lambda);
+        let bar: Vector3 = self.barycentric(&p);
+        if  1.0 >= bar.x && bar.x >= 0.0 && 
+            1.0 >= bar.y && bar.y >= 0.0 && 
+            1.0 >= bar.z && bar.z >= 0.0 {
+            Some(Intersection { lambda: intersection.lambda, barycentric: Some(bar) })
         }
         else {
-            let p: Vector3 = line.calc_point(intersection.unwrap().lambda);
-            let bar: Vector3 = self.barycentric(&p);
-            if  1.0 >= bar.x && bar.x >= 0.0 && 
-                1.0 >= bar.y && bar.y >= 0.0 && 
-                1.0 >= bar.z && bar.z >= 0.0 {
-                return Some(Intersection { lambda: intersection.unwrap().lambda, barycentric: Some(bar) });
-            }
-            else {
-                return None;
-            }
+            None
         }
     }
 }
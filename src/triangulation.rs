@@ -0,0 +1,243 @@
+use vector3::Vector3;
+use crate::base::orthonormal_basis;
+use crate::polygon3::{Polygon3, newell_normal};
+use crate::triangle3::Triangle3;
+
+// Tolerance (relative to the size of the polygon) to consider two vertexes equal, or three vertexes aligned.
+const TOLERANCE: f64 = 1e-10;
+
+// Vertex of the polygon, projected in the 2D basis of its plane
+#[derive(Clone, Copy)]
+struct Vertex {
+    p: Vector3,
+    x: f64,
+    y: f64,
+}
+
+/// Triangulates a planar polygon with holes, using ear clipping.
+/// # Arguments
+/// * `outer` - The outer loop of the polygon. Its orientation defines the normal of the polygon's `Plane`
+///   (the same way as `Polygon3`).
+/// * `holes` - The loops of the holes, in any orientation. They must be inside the outer loop and must not overlap.
+///
+/// The closing edge of each loop is implicit. Duplicated vertexes are removed, and aligned vertexes don´t produce
+/// degenerated triangles.
+/// # Returns
+/// The triangles covering the polygon, all of them defined in anticlockwise order relative to the normal of the
+/// polygon (so their normals match the polygon's one). If the outer loop is degenerated, the list is empty.
+pub fn triangulate(outer: &[Vector3], holes: &[Vec<Vector3>]) -> Vec<Triangle3> {
+    if outer.is_empty() {
+        return Vec::new();
+    }
+    let size: f64 = outer.iter().map(|p| p.distance_to(outer[0])).fold(0.0, f64::max);
+    let eps: f64 = TOLERANCE * size;
+    let area_eps: f64 = eps * size;
+
+    let outer: Vec<Vector3> = remove_duplicates(outer, eps);
+    if outer.len() < 3 {
+        return Vec::new();
+    }
+    let normal: Vector3 = newell_normal(&outer);
+    if normal.magnitude() <= area_eps {
+        return Vec::new();
+    }
+    let (u, v) = orthonormal_basis(&normal.normalize());
+
+    // All the vertexes are stored in a single array, and the loops are lists of indexes to it
+    let mut vertices: Vec<Vertex> = Vec::new();
+    let mut add_loop = |points: &[Vector3]| -> Vec<usize> {
+        let first: usize = vertices.len();
+        vertices.extend(points.iter().map(|p| Vertex { p: *p, x: p.dot(&u), y: p.dot(&v) }));
+        (first..vertices.len()).collect()
+    };
+    let ring: Vec<usize> = add_loop(&outer);
+    let mut hole_rings: Vec<Vec<usize>> = Vec::new();
+    for hole in holes {
+        let hole: Vec<Vector3> = remove_duplicates(hole, eps);
+        if hole.len() >= 3 {
+            hole_rings.push(add_loop(&hole));
+        }
+    }
+
+    // Holes must be clockwise (so once connected to the outer loop, the interior is always at the left)
+    hole_rings.retain(|hole| signed_area(&vertices, hole).abs() > area_eps);
+    for hole in hole_rings.iter_mut() {
+        if signed_area(&vertices, hole) > 0. {
+            hole.reverse();
+        }
+    }
+
+    let ring: Vec<usize> = bridge_holes(&vertices, ring, hole_rings);
+    clip_ears(&vertices, ring, area_eps)
+}
+
+impl Polygon3 {
+    /// Triangulates the polygon using ear clipping.
+    /// The triangulation is calculated once and kept by the polygon, so this only copies it (see `triangles`).
+    /// # Returns
+    /// The triangles covering the polygon, all of them defined in anticlockwise order (so their normals match the
+    /// polygon's one).
+    pub fn triangulate(&self) -> Vec<Triangle3> {
+        self.triangles().to_vec()
+    }
+}
+
+// Removes consecutive repeated vertexes (also between the last and the first one)
+fn remove_duplicates(points: &[Vector3], eps: f64) -> Vec<Vector3> {
+    let mut result: Vec<Vector3> = Vec::new();
+    for p in points {
+        if result.last().is_none_or(|last| last.distance_to(*p) > eps) {
+            result.push(*p);
+        }
+    }
+    while result.len() > 1 && result[0].distance_to(result[result.len() - 1]) <= eps {
+        result.pop();
+    }
+    result
+}
+
+// Twice the signed area of the triangle (o, a, b): positive if anticlockwise
+fn cross(o: &Vertex, a: &Vertex, b: &Vertex) -> f64 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+fn signed_area(vertices: &[Vertex], ring: &[usize]) -> f64 {
+    let mut area: f64 = 0.0;
+    for (i, &index) in ring.iter().enumerate() {
+        let a: &Vertex = &vertices[index];
+        let b: &Vertex = &vertices[ring[(i + 1) % ring.len()]];
+        area += a.x * b.y - b.x * a.y;
+    }
+    area / 2.0
+}
+
+fn same_position(a: &Vertex, b: &Vertex) -> bool {
+    a.x == b.x && a.y == b.y
+}
+
+// True if the segments (a, b) and (c, d) cross in a point that is not one of their ends
+fn segments_cross(a: &Vertex, b: &Vertex, c: &Vertex, d: &Vertex) -> bool {
+    if same_position(a, c) || same_position(a, d) || same_position(b, c) || same_position(b, d) {
+        return false;
+    }
+    let d1: f64 = cross(a, b, c);
+    let d2: f64 = cross(a, b, d);
+    if d1 == 0. && d2 == 0. {
+        // Aligned segments: they cross if they overlap
+        let (x_min, x_max) = (a.x.min(b.x).max(c.x.min(d.x)), a.x.max(b.x).min(c.x.max(d.x)));
+        let (y_min, y_max) = (a.y.min(b.y).max(c.y.min(d.y)), a.y.max(b.y).min(c.y.max(d.y)));
+        return x_min <= x_max && y_min <= y_max;
+    }
+    // Touching the other segment in the middle counts as crossing
+    d1 * d2 <= 0. && cross(c, d, a) * cross(c, d, b) <= 0.
+}
+
+// True if the point `m` is in the inner side of the corner of the ring in `p` (with previous `a` and next `b`)
+fn in_corner(a: &Vertex, p: &Vertex, b: &Vertex, m: &Vertex) -> bool {
+    if cross(a, p, b) >= 0. {
+        // Convex corner: inside the wedge from b to a
+        cross(p, b, m) >= 0. && cross(p, m, a) >= 0.
+    } else {
+        // Reflex corner: anything but the (convex) outer wedge from a to b
+        !(cross(p, a, m) > 0. && cross(p, m, b) > 0.)
+    }
+}
+
+// Connects each hole to the outer ring with a pair of coincident edges ("bridge"), so the result is a single ring
+fn bridge_holes(vertices: &[Vertex], mut ring: Vec<usize>, mut holes: Vec<Vec<usize>>) -> Vec<usize> {
+    // Holes are processed from right to left, so the bridges never cross the holes not processed yet
+    let rightmost = |hole: &Vec<usize>| hole.iter().map(|&i| vertices[i].x).fold(f64::NEG_INFINITY, f64::max);
+    holes.sort_by(|h1, h2| rightmost(h2).total_cmp(&rightmost(h1)));
+
+    for (h, hole) in holes.iter().enumerate() {
+        let start: usize = (0..hole.len()).max_by(|&i, &j| vertices[hole[i]].x.total_cmp(&vertices[hole[j]].x)).unwrap();
+        let m: &Vertex = &vertices[hole[start]];
+
+        // Closest vertex of the ring that can be connected to m without crossing any edge
+        let mut candidates: Vec<usize> = (0..ring.len()).collect();
+        candidates.sort_by(|&i, &j| {
+            let di: f64 = (vertices[ring[i]].x - m.x).powi(2) + (vertices[ring[i]].y - m.y).powi(2);
+            let dj: f64 = (vertices[ring[j]].x - m.x).powi(2) + (vertices[ring[j]].y - m.y).powi(2);
+            di.total_cmp(&dj)
+        });
+        let visible = |i: &usize| -> bool {
+            let p: &Vertex = &vertices[ring[*i]];
+            let a: &Vertex = &vertices[ring[(*i + ring.len() - 1) % ring.len()]];
+            let b: &Vertex = &vertices[ring[(*i + 1) % ring.len()]];
+            if !in_corner(a, p, b, m) {
+                return false;
+            }
+            let crosses_loop = |lp: &[usize]| {
+                (0..lp.len()).any(|k| segments_cross(m, p, &vertices[lp[k]], &vertices[lp[(k + 1) % lp.len()]]))
+            };
+            !crosses_loop(&ring) && !holes[h..].iter().any(|other| crosses_loop(other))
+        };
+        let Some(pi) = candidates.iter().find(|i| visible(i)).copied() else {
+            continue;
+        };
+
+        // ring[..=pi], then the hole starting and ending at m, and back to ring[pi]
+        let mut bridged: Vec<usize> = ring[..=pi].to_vec();
+        bridged.extend((0..=hole.len()).map(|k| hole[(start + k) % hole.len()]));
+        bridged.extend_from_slice(&ring[pi..]);
+        ring = bridged;
+    }
+    ring
+}
+
+// Ear clipping of an anticlockwise ring
+fn clip_ears(vertices: &[Vertex], mut ring: Vec<usize>, eps: f64) -> Vec<Triangle3> {
+    let mut triangles: Vec<Triangle3> = Vec::new();
+
+    while ring.len() >= 3 {
+        let n: usize = ring.len();
+        let corner = |i: usize| (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+
+        // Aligned (or folded) vertexes are removed without producing a triangle
+        if let Some(i) = (0..n).find(|&i| {
+            let (a, b, c) = corner(i);
+            cross(&vertices[a], &vertices[b], &vertices[c]).abs() <= eps
+        }) {
+            ring.remove(i);
+            continue;
+        }
+        if n == 3 {
+            let (a, b, c) = corner(1);
+            triangles.push(Triangle3::new(&vertices[a].p, &vertices[b].p, &vertices[c].p));
+            break;
+        }
+
+        let is_ear = |i: usize| -> bool {
+            let (a, b, c) = corner(i);
+            let (va, vb, vc) = (&vertices[a], &vertices[b], &vertices[c]);
+            if cross(va, vb, vc) <= 0. {
+                return false;
+            }
+            // No other vertex can be inside the triangle (or in its border)
+            ring.iter().all(|&k| {
+                let p: &Vertex = &vertices[k];
+                same_position(p, va) || same_position(p, vb) || same_position(p, vc) ||
+                    cross(va, vb, p) < 0. || cross(vb, vc, p) < 0. || cross(vc, va, p) < 0.
+            })
+        };
+        // If numerical issues prevent finding a proper ear, the most convex corner is clipped anyway
+        let ear: Option<usize> = (0..n).find(|&i| is_ear(i)).or_else(|| {
+            (0..n).filter(|&i| {
+                let (a, b, c) = corner(i);
+                cross(&vertices[a], &vertices[b], &vertices[c]) > 0.
+            }).max_by(|&i, &j| {
+                let (a, b, c) = corner(i);
+                let (d, e, f) = corner(j);
+                cross(&vertices[a], &vertices[b], &vertices[c]).total_cmp(&cross(&vertices[d], &vertices[e], &vertices[f]))
+            })
+        });
+        let Some(i) = ear else {
+            break;
+        };
+
+        let (a, b, c) = corner(i);
+        triangles.push(Triangle3::new(&vertices[a].p, &vertices[b].p, &vertices[c].p));
+        ring.remove(i);
+    }
+    triangles
+}